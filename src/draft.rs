@@ -0,0 +1,54 @@
+
+/// Pick order of a snake draft, where the order of the teams is reversed
+/// every round.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DraftState {
+    pub num_teams: usize,
+    /// Overall pick number, starting from 0
+    pub current_pick: usize,
+    /// Team index and player name of every pick made so far
+    pub picks: Vec<(usize, String)>,
+}
+
+impl DraftState {
+    pub fn new(num_teams: usize) -> DraftState {
+        DraftState {
+            num_teams: num_teams.max(1),
+            current_pick: 0,
+            picks: Vec::new(),
+        }
+    }
+
+    /// Current round, starting from 1
+    pub fn round(&self) -> usize {
+        self.current_pick / self.num_teams + 1
+    }
+
+    /// Index of the team that is on the clock
+    pub fn on_the_clock(&self) -> usize {
+        let index = self.current_pick % self.num_teams;
+        if self.round().is_multiple_of(2) {
+            self.num_teams - 1 - index
+        } else {
+            index
+        }
+    }
+
+    /// Assigns the player to the team on the clock and moves to the next pick.
+    pub fn pick(&mut self, name: String) {
+        self.picks.push((self.on_the_clock(), name));
+        self.current_pick += 1;
+    }
+
+    /// Takes the player's pick out of the draft, wherever it was. The picks
+    /// after it move up a slot, as if it had never been made.
+    pub fn remove(&mut self, name: &str) {
+        if let Some(index) = self.picks.iter().position(|(_, pick)| pick == name) {
+            let later: Vec<String> = self.picks.drain(index..).skip(1).map(|(_, pick)| pick).collect();
+            self.current_pick = index;
+            for pick in later {
+                self.pick(pick);
+            }
+        }
+    }
+}
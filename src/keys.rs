@@ -0,0 +1,64 @@
+use serde::Deserialize;
+
+/// Keys of the remappable actions, read from keys.toml. Actions missing from
+/// the file keep their default key.
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct KeyBindings {
+    pub search: char,
+    pub add_to_my_team: char,
+    pub add_to_other_team: char,
+    pub list: char,
+    pub quit: char,
+}
+
+impl Default for KeyBindings {
+    fn default() -> KeyBindings {
+        KeyBindings {
+            search: 's',
+            add_to_my_team: 'a',
+            add_to_other_team: 'b',
+            list: 'l',
+            quit: 'q',
+        }
+    }
+}
+
+/// Keys of the idle mode that can't be remapped
+const IDLE_KEYS: &[char] = &['f', 'b', 'd', 'c', 'r', 'm', 'p', '0'];
+
+/// Keys of the picking mode that can't be remapped
+const PICKING_KEYS: &[char] = &['n', 'o'];
+
+impl KeyBindings {
+    /// Whether the pressed key triggers the action bound to `binding`,
+    /// ignoring case.
+    pub fn matches(pressed: char, binding: char) -> bool {
+        pressed.eq_ignore_ascii_case(&binding)
+    }
+
+    /// Descriptions of the actions that share a key with another action of
+    /// the same mode.
+    pub fn conflicts(&self) -> Vec<String> {
+        let idle = [("search", self.search), ("list", self.list), ("quit", self.quit)];
+        let picking = [
+            ("add_to_my_team", self.add_to_my_team),
+            ("add_to_other_team", self.add_to_other_team),
+        ];
+
+        let mut conflicts = Vec::new();
+        for (actions, fixed) in [(&idle[..], IDLE_KEYS), (&picking[..], PICKING_KEYS)] {
+            for (i, (action, key)) in actions.iter().enumerate() {
+                for (other, other_key) in actions.iter().skip(i + 1) {
+                    if KeyBindings::matches(*key, *other_key) {
+                        conflicts.push(format!("{} and {} are both bound to {}", action, other, key));
+                    }
+                }
+                if fixed.iter().any(|f| KeyBindings::matches(*key, *f)) {
+                    conflicts.push(format!("{} is bound to {}, which is already taken", action, key));
+                }
+            }
+        }
+        conflicts
+    }
+}
@@ -5,37 +5,53 @@
 ///
 /// This is a very simple example:
 ///   * A input box always focused. Every character you type is registered
-///   here
+///     here
 ///   * Pressing Backspace erases a character
 ///   * Pressing Enter pushes the current input in the history of previous
-///   messages
+///     messages
 use crossterm::{
-    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode},
+    cursor,
+    event::{
+        self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyModifiers, MouseButton,
+        MouseEvent, MouseEventKind,
+    },
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
-use std::{error::Error, io};
+use std::{
+    cmp::Ordering,
+    collections::{HashMap, VecDeque},
+    error::Error,
+    io,
+    time::{Duration, Instant},
+};
 use tui::{
     backend::{Backend, CrosstermBackend},
-    layout::{Constraint, Direction, Layout},
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Span, Spans, Text},
-    widgets::{Block, Borders, List, ListItem, Paragraph},
+    widgets::{Block, Borders, Cell, Clear, Gauge, List, ListItem, ListState, Paragraph, Row, Table, Wrap},
     Frame, Terminal,
 };
-use unicode_width::UnicodeWidthStr;
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
 
 use serde::{Deserialize, Serialize};
-use serde_json;
 use std::fs::File;
 use std::io::Write;
 
 use std::env;
+use std::path::{Path, PathBuf};
 
 
+pub mod draft;
+pub mod keys;
 pub mod positions;
+pub mod search;
 
+use crate::draft::*;
+use crate::keys::KeyBindings;
 use crate::positions::*;
+use crate::search::{match_rank, normalize};
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 struct Player {
@@ -46,15 +62,454 @@ struct Player {
     pick_avg: f32,
     round_avg: f32,
     draft_percent: String,
+    /// Games the player's team plays in the fantasy season, if the dataset has it
+    #[serde(default)]
+    games: Option<u32>,
+    /// Overall rank published by the fantasy site, if the dataset has it
+    #[serde(default)]
+    rank: Option<u32>,
+    /// Labels like "rookie" or "injured", searchable with `#tag`
+    #[serde(default)]
+    tags: Vec<String>,
+}
+
+impl Player {
+    /// Whether the player shows up under the position filter or fits the
+    /// slot. Players without a listed position only fit the ones anyone fits.
+    fn eligible_for(&self, group: &Position) -> bool {
+        if self.position.is_empty() {
+            return group.accepts_anyone();
+        }
+        self.position.iter().any(|p| p.does_position_belong(group))
+    }
+
+    /// Positions with the primary one first, e.g. "SG (PG)".
+    fn position_label(&self) -> String {
+        Position::label(&self.position)
+    }
+
+    /// Draft percentage as a number, e.g. 85.0 for "85%", or 0.0 if it can't
+    /// be parsed.
+    fn draft_percent_value(&self) -> f32 {
+        self.draft_percent
+            .trim()
+            .trim_end_matches('%')
+            .parse()
+            .unwrap_or(0.0)
+    }
+}
+
+/// A roster slot definition in slots.json
+#[derive(Serialize, Deserialize, Debug)]
+struct SlotConfig {
+    position: Position,
+    count: u16,
+}
+
+/// A drafted player in my_players.json and other_players.json
+#[derive(Serialize, Deserialize, Debug)]
+struct PlayerPick {
+    name: String,
+    /// Round the player was drafted in, 0 if unknown
+    #[serde(default)]
+    round: u16,
+}
+
+/// Version of the draft file format written by `save_players`
+const SAVE_FILE_VERSION: u32 = 1;
+
+/// Contents of my_players.json and other_players.json
+#[derive(Serialize, Deserialize, Debug)]
+struct SaveFile {
+    version: u32,
+    players: Vec<PlayerPick>,
+}
+
+/// Every format the draft files were ever written in
+#[derive(Deserialize, Debug)]
+#[serde(untagged)]
+enum AnySaveFile {
+    Versioned(SaveFile),
+    /// Version 0, a bare array of picks
+    Legacy(Vec<SavedPick>),
+}
+
+/// A pick in a version 0 draft file, the oldest of which only hold names
+#[derive(Deserialize, Debug)]
+#[serde(untagged)]
+enum SavedPick {
+    Pick(PlayerPick),
+    Name(String),
+}
+
+impl From<SavedPick> for PlayerPick {
+    fn from(saved: SavedPick) -> PlayerPick {
+        match saved {
+            SavedPick::Pick(pick) => pick,
+            SavedPick::Name(name) => PlayerPick { name, round: 0 },
+        }
+    }
+}
+
+/// Contents of the file passed to `--keepers`: players kept from last season
+/// on my team and on the other teams. A bare array lists only mine.
+#[derive(Deserialize, Debug)]
+#[serde(untagged)]
+enum KeeperFile {
+    Teams {
+        #[serde(default)]
+        mine: Vec<String>,
+        #[serde(default)]
+        other: Vec<String>,
+    },
+    Mine(Vec<String>),
+}
+
+/// Session state saved alongside the player files and restored with `load`.
+/// Missing fields fall back to their defaults.
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(default)]
+struct Session {
+    selected_position: Position,
+    /// Missing from sessions saved before sorting was kept, which then keep
+    /// the default for the dataset
+    sort_mode: Option<SortMode>,
+    display_stat: DisplayStat,
+    min_draft_percent: Option<f32>,
+    max_pick_avg: Option<f32>,
+    /// Drafted players in pick order, to rebuild the draft board
+    picks: Vec<String>,
+}
+
+impl Default for Session {
+    fn default() -> Self {
+        Session {
+            selected_position: Position::ANY,
+            sort_mode: None,
+            display_stat: DisplayStat::PickAvg,
+            min_draft_percent: None,
+            max_pick_avg: None,
+            picks: Vec::new(),
+        }
+    }
 }
 
+/// Summary of the undrafted players, see `App::pool_stats`
+struct PoolStats {
+    count: usize,
+    /// Average of their average picks, 0 if nobody is left
+    avg_pick: f32,
+    /// Number of players by primary position
+    by_position: Vec<(Position, usize)>,
+}
 
-#[derive(Eq, PartialEq, Debug)]
+#[derive(Eq, PartialEq, Debug, Clone, Copy)]
 enum InputMode {
     Idle,
     Searching,
     Picking,
     Listing,
+    PositionJump,
+    Recommending,
+    Comparing,
+    Noting,
+    Threshold,
+    Board,
+}
+
+/// Numeric filter being set in threshold mode
+#[derive(Eq, PartialEq, Debug, Clone, Copy)]
+enum Threshold {
+    MinDraftPercent,
+    MaxPickAvg,
+}
+
+impl Threshold {
+    fn label(&self) -> &'static str {
+        match self {
+            Threshold::MinDraftPercent => "Minimum draft %",
+            Threshold::MaxPickAvg => "Maximum pick avg",
+        }
+    }
+}
+
+/// File my roster is exported to from the listing
+#[derive(Eq, PartialEq, Debug, Clone, Copy)]
+enum Export {
+    Csv,
+    Markdown,
+}
+
+impl Export {
+    fn filename(&self) -> &'static str {
+        match self {
+            Export::Csv => "my_players.csv",
+            Export::Markdown => "draft_summary.md",
+        }
+    }
+}
+
+/// A pick made during the draft, kept so it can be undone
+#[derive(Eq, PartialEq, Debug, Clone)]
+enum DraftAction {
+    /// Player was added to my team
+    Mine(String),
+    /// Player was added to the other team
+    Other(String),
+}
+
+/// Players drafted in more than this percentage of leagues are highlighted
+const HIGH_DRAFT_PERCENT: f32 = 50.0;
+
+/// How many picks a player's average draft position has to differ from the
+/// current pick before they are highlighted as a value or a reach
+const ADP_THRESHOLD: f32 = 10.0;
+
+/// Number of players shown in the best available panel
+const BEST_AVAILABLE_LIMIT: usize = 10;
+
+/// Drafting this many players from the same NBA team is highlighted as a risk
+const TEAM_CONCENTRATION_WARNING: usize = 3;
+
+/// A drop in average pick bigger than this between two players starts a new tier
+const TIER_GAP: f32 = 3.0;
+
+/// Positions with fewer undrafted players than this are highlighted as scarce
+const SCARCITY_THRESHOLD: usize = 5;
+
+/// Number of picks shown in the recent picks ticker
+const RECENT_PICKS_LIMIT: usize = 10;
+
+/// Maximum number of draft actions that can be undone
+const HISTORY_LIMIT: usize = 50;
+
+/// Share of my roster drafted after which unfilled positions are warned about
+const LATE_DRAFT_SHARE: f32 = 0.5;
+
+/// The draft clock turns red with fewer seconds left than this
+const CLOCK_WARNING_SECS: u64 = 10;
+
+/// How often the screen is redrawn while the draft clock runs
+const CLOCK_TICK: Duration = Duration::from_millis(250);
+
+/// Display width of the name column in the player list, markers included
+const NAME_WIDTH: usize = 24;
+
+/// Names are never cut shorter than this, however narrow the terminal
+const MIN_NAME_WIDTH: usize = 8;
+
+/// Display width of the player list row besides the rank and name columns:
+/// row number, position, stat, games, draft percentage and tier
+const LIST_COLUMNS_WIDTH: usize = 4 + 10 + 1 + 5 + 5 + 6 + 3;
+
+/// Display width of the stat column in the player list
+const STAT_WIDTH: usize = 5;
+
+/// Every keybinding by mode, shown in the `?` help overlay
+const KEYBINDINGS: &[(&str, &[(&str, &str)])] = &[
+    ("Anywhere", &[
+        ("Left/Right", "change the position filter"),
+        ("?", "show or hide this help"),
+    ]),
+    ("Idle", &[
+        ("s, Enter", "start searching, so do Up/Down"),
+        ("l", "list my roster"),
+        ("f", "jump to a position"),
+        ("b", "best available players"),
+        ("d", "show the draft board"),
+        ("0", "show every position"),
+        ("c", "collapse or expand the position row"),
+        ("r", "clear the search, position, thresholds and sorting"),
+        ("R", "read the player data again"),
+        ("Backspace", "back to the previous position"),
+        ("m", "set a minimum draft %, empty clears it"),
+        ("p", "set a maximum pick avg, empty clears it"),
+        ("q", "quit"),
+    ]),
+    ("Searching", &[
+        ("type", "search players by name"),
+        ("@TEAM", "only players of a team, e.g. @LAL james"),
+        ("#tag", "only players with a tag, e.g. #rookie"),
+        ("Up/Down", "select a player, Up on an empty input recalls past searches"),
+        ("Home/End", "select the first/last player"),
+        ("Tab", "accept the grey completion, or complete the top match"),
+        ("Backspace", "back to the previous position, before typing"),
+        ("Enter", "pick the selected player"),
+        ("1-9", "complete a row on screen"),
+        ("0", "show every position"),
+        ("+/-", "show more or fewer players, before typing"),
+        ("Alt+letter", "jump to the first player starting with the letter"),
+        ("Ctrl+U", "clear the search"),
+        ("Ctrl+L", "clear the search, position, thresholds and sorting"),
+        ("Ctrl+Y", "copy the player's name"),
+        ("Ctrl+S", "change sorting"),
+        ("Ctrl+D", "change the stat shown after the position"),
+        ("Ctrl+Z", "undo the last pick"),
+        ("Ctrl+A", "draft the best available player to my team right away"),
+        ("Ctrl+F", "jump to a position"),
+        ("Ctrl+N", "filter by the position I need most"),
+        ("Ctrl+V", "compare two players"),
+        ("Ctrl+W", "add or remove the player from the watchlist"),
+        ("Ctrl+X", "never show the player again, until un-excluded in the listing"),
+        ("Ctrl+T", "show how many of the player's teammates I have"),
+        ("Ctrl+O", "show everything about the player, Esc closes it"),
+        ("Ctrl+R", "search the drafted players instead, Enter removes them from the draft"),
+        ("Esc", "stop searching"),
+    ]),
+    ("Picking", &[
+        ("A, Enter", "add to my team"),
+        ("B", "add to the other team"),
+        ("O", "add to my team even if the roster is full"),
+        ("N", "write a note, Enter saves it and Esc cancels"),
+        ("Esc", "go back"),
+    ]),
+    ("Best available", &[
+        ("Up/Down", "select a player"),
+        ("j/k, g/G", "move down/up, to the top/bottom"),
+        ("Backspace", "back to the previous position"),
+        ("Enter", "pick the selected player"),
+        ("Alt+letter", "jump to the first player starting with the letter"),
+        ("Esc, b, q", "go back to idle"),
+    ]),
+    ("Listing", &[
+        ("Up/Down", "select a slot"),
+        ("Tab", "switch between my team and the other team"),
+        ("Shift+Up/Down", "move the player up or down the roster order, which decides the slots"),
+        ("D", "remove the player"),
+        ("M", "move the player to the other team"),
+        ("O", "show the other team"),
+        ("W", "show the watchlist, D un-watches one"),
+        ("H", "show the excluded players, D un-excludes one"),
+        ("E", "export to CSV, Y confirms if slots are empty"),
+        ("X", "export a Markdown summary, Y confirms if slots are empty"),
+        ("q", "go back to idle"),
+    ]),
+    ("Position jump", &[
+        ("type", "a, pg, sg, sf, pf, c, f, g, t, sh, u or b, anything else cancels"),
+    ]),
+    ("Draft board", &[
+        ("Esc, d, q", "go back to idle"),
+    ]),
+    ("Comparing", &[
+        ("Esc", "clear the comparison"),
+    ]),
+    ("Mouse", &[
+        ("click", "select a player, click again to pick them"),
+        ("wheel", "move the selection"),
+    ]),
+];
+
+/// Order in which the filtered players are listed. Players are always ranked
+/// by how well they match the search query first, the sort mode breaks ties.
+#[derive(Serialize, Deserialize, Eq, PartialEq, Debug, Clone, Copy)]
+enum SortMode {
+    Rank,
+    PickAvg,
+    RoundAvg,
+    DraftPercent,
+    Alphabetical,
+    Games,
+}
+
+impl SortMode {
+    /// Rank when the data set ranks players, pick avg otherwise
+    fn default_for(has_ranks: bool) -> SortMode {
+        if has_ranks {
+            SortMode::Rank
+        } else {
+            SortMode::PickAvg
+        }
+    }
+
+    fn next(&self) -> SortMode {
+        match self {
+            SortMode::Rank => SortMode::PickAvg,
+            SortMode::PickAvg => SortMode::RoundAvg,
+            SortMode::RoundAvg => SortMode::DraftPercent,
+            SortMode::DraftPercent => SortMode::Alphabetical,
+            SortMode::Alphabetical => SortMode::Games,
+            SortMode::Games => SortMode::Rank,
+        }
+    }
+
+    fn label(&self) -> &'static str {
+        match self {
+            SortMode::Rank => "rank",
+            SortMode::PickAvg => "pick avg",
+            SortMode::RoundAvg => "round avg",
+            SortMode::DraftPercent => "draft %",
+            SortMode::Alphabetical => "name",
+            SortMode::Games => "games",
+        }
+    }
+
+    /// Lower average pick and round means a more valuable player, so both
+    /// sort ascending. Players drafted in more leagues are more valuable, so
+    /// draft percentage sorts descending. So do games, with players without a
+    /// game count last. Rank sorts ascending, with unranked players last.
+    fn compare(&self, a: &Player, b: &Player) -> Ordering {
+        match self {
+            SortMode::Rank => match (a.rank, b.rank) {
+                (Some(a_rank), Some(b_rank)) => a_rank.cmp(&b_rank),
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (None, None) => Ordering::Equal,
+            }
+            .then(a.pick_avg.total_cmp(&b.pick_avg)),
+            SortMode::PickAvg => a
+                .pick_avg
+                .total_cmp(&b.pick_avg)
+                .then(a.round_avg.total_cmp(&b.round_avg)),
+            SortMode::RoundAvg => a
+                .round_avg
+                .total_cmp(&b.round_avg)
+                .then(a.pick_avg.total_cmp(&b.pick_avg)),
+            SortMode::DraftPercent => b
+                .draft_percent_value()
+                .total_cmp(&a.draft_percent_value())
+                .then(a.pick_avg.total_cmp(&b.pick_avg)),
+            SortMode::Alphabetical => a.name.cmp(&b.name),
+            SortMode::Games => b.games.cmp(&a.games).then(a.pick_avg.total_cmp(&b.pick_avg)),
+        }
+    }
+}
+
+/// Stat shown in the search results after the player's position
+#[derive(Serialize, Deserialize, Eq, PartialEq, Debug, Clone, Copy)]
+enum DisplayStat {
+    PickAvg,
+    RoundAvg,
+    DraftPercent,
+    Team,
+}
+
+impl DisplayStat {
+    fn next(&self) -> DisplayStat {
+        match self {
+            DisplayStat::PickAvg => DisplayStat::RoundAvg,
+            DisplayStat::RoundAvg => DisplayStat::DraftPercent,
+            DisplayStat::DraftPercent => DisplayStat::Team,
+            DisplayStat::Team => DisplayStat::PickAvg,
+        }
+    }
+
+    fn label(&self) -> &'static str {
+        match self {
+            DisplayStat::PickAvg => "pick avg",
+            DisplayStat::RoundAvg => "round avg",
+            DisplayStat::DraftPercent => "draft %",
+            DisplayStat::Team => "team",
+        }
+    }
+
+    fn value(&self, player: &Player) -> String {
+        match self {
+            DisplayStat::PickAvg => format!("{:.1}", player.pick_avg),
+            DisplayStat::RoundAvg => format!("{:.1}", player.round_avg),
+            DisplayStat::DraftPercent => player.draft_percent.clone(),
+            DisplayStat::Team => player.team.clone(),
+        }
+    }
 }
 
 /// App holds the state of the application
@@ -65,18 +520,152 @@ struct App {
     input_mode: InputMode,
     /// List of all players
     all_players: Vec<Player>,
+    /// Whether any player in the dataset has a rank
+    has_ranks: bool,
+    /// Path or URL the players were loaded from, read again by `reload_dataset`
+    data_source: String,
+    /// Whether `data_source` is a CSV file rather than JSON
+    csv_source: bool,
+    /// Normalized name of every player, in the same order as `all_players`,
+    /// so searching doesn't normalize every name on every keystroke
+    normalized_names: Vec<String>,
     /// My players
     my_players: Vec<String>,
     /// Other's players
     other_players: Vec<String>,
     /// Filtered list of players
     filtered_players: Vec<String>,
+    /// Number of players matching the search, including the ones scrolled
+    /// out of view
+    match_count: usize,
+    /// Indices of the name characters that matched the search, by player name
+    match_positions: HashMap<String, Vec<usize>>,
+    /// Search input of the last `filter_players`
+    last_query: String,
+    /// Indices in `all_players` of the players that matched `last_query`,
+    /// `None` after showing the best available players
+    last_candidates: Option<Vec<usize>>,
+    /// Rest of the names that start with the search, shown as ghost text
+    /// after the input and accepted with Tab
+    completion: String,
+    /// Number of undrafted players left at every position, refreshed with
+    /// the filtered players
+    remaining: Vec<(Position, usize)>,
+    /// Tier of every filtered player, starting from 1
+    tiers: HashMap<String, usize>,
+    /// Latest picks of this session with the team they went to, most recent last
+    recent_picks: VecDeque<String>,
     /// Current selected player
     selected_player: Option<usize>,
     /// Candidate player
     candidate_player: String,
     /// selected position
     selected_position: Position,
+    /// Order of the filtered players
+    sort_mode: SortMode,
+    /// Stat shown after the position in the search results
+    display_stat: DisplayStat,
+    /// Position filter before the last change
+    prev_position: Position,
+    /// Selected roster slot in the listing
+    selected_slot: usize,
+    /// List the other team's roster instead of mine
+    list_other_team: bool,
+    /// Draft actions that can be undone, most recent last
+    history: Vec<DraftAction>,
+    /// Directory the draft files are saved in
+    dir: PathBuf,
+    /// Directory of the rosters and the session, `dir` itself or the
+    /// profile's directory under it
+    draft_dir: PathBuf,
+    /// Whether the saved draft was loaded with `load`
+    resumed: bool,
+    /// Index of the first filtered player shown in the list
+    scroll_offset: usize,
+    /// Number of players shown in the list, updated on every draw to the rows
+    /// that fit on screen or to `manual_result_limit` if it is smaller
+    result_limit: usize,
+    /// Number of players to show set with +/-, if any
+    manual_result_limit: Option<usize>,
+    /// Show the other team's roster next to mine in the listing
+    side_by_side: bool,
+    /// Roster loaded with `--compare`, only ever shown
+    compare_players: Vec<String>,
+    /// Players on a roster before the draft started, loaded with `--keepers`
+    keepers: Vec<String>,
+    /// Time every team has for a pick, if the draft is timed
+    pick_clock: Option<Duration>,
+    /// When the current pick's time runs out
+    pick_deadline: Option<Instant>,
+    /// Draft the best available player for me when the time runs out
+    auto_pick: bool,
+    /// Index of my team in the draft order, if known
+    my_slot: Option<usize>,
+    /// Spell out what colors mean and don't blink, for screen readers
+    accessible: bool,
+    /// System clipboard, opened on the first copy and kept open, since on
+    /// X11 the copied text is gone once it closes
+    clipboard: Option<arboard::Clipboard>,
+    /// Keys typed so far in position jump mode
+    jump_keys: String,
+    /// Mode to go back to once the position jump is done
+    jump_return_mode: InputMode,
+    /// Snake draft pick order
+    draft: DraftState,
+    /// One-off message shown in the help bar until the next key press
+    status_message: Option<String>,
+    /// Mode to go back to once the candidate player is picked
+    pick_return_mode: InputMode,
+    /// Players picked for a side by side comparison, at most two
+    comparison: Vec<String>,
+    /// Roster slots of the league and how many players each one holds
+    slots: Vec<(Position, u16)>,
+    /// Screen area of the player list, updated on every draw
+    list_area: Rect,
+    /// The search query as typed, before any autocompletion
+    typed_query: String,
+    /// Past search queries that led to a pick, most recent last
+    query_history: Vec<String>,
+    /// Position in `query_history` while recalling past queries
+    history_index: Option<usize>,
+    /// Show the keybinding overlay over everything else
+    show_help: bool,
+    /// Player shown in the detail card, if it's open
+    detail_player: Option<String>,
+    /// Export waiting for a yes because the roster has empty slots
+    pending_export: Option<Export>,
+    /// Drafted player waiting for a yes before being taken off the draft
+    pending_removal: Option<String>,
+    /// Keys of the remappable actions
+    keys: KeyBindings,
+    /// Notes attached to players, by player name
+    notes: HashMap<String, String>,
+    /// Note being typed for the candidate player
+    note_input: String,
+    /// Players flagged as targets, they stay searchable until drafted
+    watchlist: Vec<String>,
+    /// Players I never want to draft, hidden from search
+    excluded: Vec<String>,
+    /// Round every drafted player was picked in, by player name
+    rounds: HashMap<String, u16>,
+    /// Show the watchlist instead of my roster in the listing
+    show_watchlist: bool,
+    /// Show the excluded players instead of my roster in the listing
+    show_excluded: bool,
+    /// Annotate search results with how many of their NBA teammates I have
+    show_teammates: bool,
+    /// Show every position at the bottom, or only the selected one
+    show_position_picker: bool,
+    /// Search the drafted players instead of the undrafted ones
+    show_drafted: bool,
+    /// Hide players drafted in fewer leagues than this percentage
+    min_draft_percent: Option<f32>,
+    /// Hide players with a higher average pick than this
+    max_pick_avg: Option<f32>,
+    /// Threshold being set in threshold mode
+    threshold: Threshold,
+    /// Value typed so far in threshold mode
+    threshold_input: String,
 }
 
 impl Default for App {
@@ -85,234 +674,2598 @@ impl Default for App {
             input: String::new(),
             input_mode: InputMode::Idle,
             all_players: Vec::new(),
+            has_ranks: false,
+            data_source: String::new(),
+            csv_source: false,
+            normalized_names: Vec::new(),
             my_players: Vec::new(),
             other_players: Vec::new(),
             filtered_players: Vec::new(),
+            match_count: 0,
+            completion: String::new(),
+            match_positions: HashMap::new(),
+            last_query: String::new(),
+            last_candidates: None,
+            remaining: Vec::new(),
+            tiers: HashMap::new(),
+            recent_picks: VecDeque::new(),
             selected_player: None,
             candidate_player: String::new(),
             selected_position: Position::ANY,
+            sort_mode: SortMode::PickAvg,
+            display_stat: DisplayStat::PickAvg,
+            prev_position: Position::ANY,
+            selected_slot: 0,
+            list_other_team: false,
+            history: Vec::new(),
+            dir: PathBuf::from("."),
+            draft_dir: PathBuf::from("."),
+            resumed: false,
+            scroll_offset: 0,
+            result_limit: 8,
+            manual_result_limit: None,
+            side_by_side: false,
+            compare_players: Vec::new(),
+            keepers: Vec::new(),
+            pick_clock: None,
+            pick_deadline: None,
+            auto_pick: false,
+            my_slot: None,
+            accessible: false,
+            clipboard: None,
+            jump_keys: String::new(),
+            jump_return_mode: InputMode::Idle,
+            draft: DraftState::new(12),
+            status_message: None,
+            pick_return_mode: InputMode::Searching,
+            comparison: Vec::new(),
+            slots: App::default_slots(),
+            list_area: Rect::default(),
+            typed_query: String::new(),
+            query_history: Vec::new(),
+            history_index: None,
+            show_help: false,
+            detail_player: None,
+            pending_export: None,
+            pending_removal: None,
+            keys: KeyBindings::default(),
+            notes: HashMap::new(),
+            note_input: String::new(),
+            watchlist: Vec::new(),
+            excluded: Vec::new(),
+            rounds: HashMap::new(),
+            show_watchlist: false,
+            show_excluded: false,
+            show_teammates: false,
+            show_position_picker: true,
+            show_drafted: false,
+            min_draft_percent: None,
+            max_pick_avg: None,
+            threshold: Threshold::MinDraftPercent,
+            threshold_input: String::new(),
         }
     }
 }
 
 impl App {
+    /// Searches the players for the input. Typing onto the last search only
+    /// narrows it down, so then only the last matches are searched again.
+    /// Anything else, like Backspace or a draft, searches every player.
     fn filter_players(&mut self) {
-        self.filtered_players = self
+        let (team, tag, query) = parse_search_filters(&self.input);
+        let (last_team, last_tag, _) = parse_search_filters(&self.last_query);
+        // every other filter calls this as soon as it changes, with the same input
+        let narrowing = self.input.len() > self.last_query.len()
+            && self.input.starts_with(&self.last_query)
+            && team == last_team
+            && tag == last_tag;
+        let candidates = match self.last_candidates.take() {
+            Some(candidates) if narrowing => candidates,
+            _ => {
+                // the counts don't depend on the search
+                self.remaining = Position::get_all_positions()
+                    .into_iter()
+                    .map(|position| {
+                        let count = self.remaining_at(&position);
+                        (position, count)
+                    })
+                    .collect();
+                (0..self.all_players.len()).collect()
+            }
+        };
+
+        if self.input_mode == InputMode::Recommending {
+            self.filtered_players = self.best_available(BEST_AVAILABLE_LIMIT);
+            self.match_count = self.filtered_players.len();
+            self.match_positions.clear();
+            self.completion.clear();
+            self.tiers = self.tier_numbers();
+            self.scroll_offset = 0;
+            self.clamp_selection();
+            return;
+        }
+
+        let query = normalize(query);
+        let mut match_positions: HashMap<String, Vec<usize>> = HashMap::new();
+        let mut scored: Vec<(u8, i32, usize)> = candidates
+            .into_iter()
+            .map(|i| (i, &self.all_players[i], &self.normalized_names[i]))
+            .filter(|(_, p, _)|
+                (self.my_players.contains(&p.name) || self.other_players.contains(&p.name))
+                    == self.show_drafted
+                && !self.excluded.contains(&p.name)
+                && p.eligible_for(&self.selected_position)
+                && team.is_none_or(|team| p.team.eq_ignore_ascii_case(team))
+                && tag.is_none_or(|tag| p.tags.iter().any(|t| t.eq_ignore_ascii_case(tag)))
+                && self.passes_thresholds(p)
+            )
+            .filter_map(|(i, p, name)| {
+                self.fuzzy_score(&query, name).map(|(score, matched)| {
+                    if !matched.is_empty() {
+                        match_positions.insert(p.name.clone(), matched);
+                    }
+                    (match_rank(&query, name), score, i)
+                })
+            })
+            .collect();
+
+        scored.sort_by(|(a_rank, a_score, a), (b_rank, b_score, b)| {
+            b_rank
+                .cmp(a_rank)
+                .then(b_score.cmp(a_score))
+                .then(self.sort_mode.compare(&self.all_players[*a], &self.all_players[*b]))
+        });
+
+        self.filtered_players = scored
+            .iter()
+            .map(|(_, _, i)| self.all_players[*i].name.clone())
+            .collect();
+        let matches: Vec<usize> = scored.into_iter().map(|(_, _, i)| i).collect();
+        self.completion = self.common_completion(&query, &matches);
+        self.last_candidates = Some(matches);
+        self.last_query = self.input.clone();
+        self.match_count = self.filtered_players.len();
+        self.match_positions = match_positions;
+        self.tiers = self.tier_numbers();
+
+        // the list may have shrunk past the selected player
+        self.scroll_offset = 0;
+        self.clamp_selection();
+    }
+
+    /// What the normalized query can be completed with: the rest of the top
+    /// match, or only the part it shares with the other matches starting with
+    /// the query. Empty unless the top match starts with the query. `matches`
+    /// are the indices in `all_players` of the matches, best first.
+    fn common_completion(&self, query: &str, matches: &[usize]) -> String {
+        let starts_with_query = |i: &usize| {
+            let (name, normalized) = (&self.all_players[*i].name, &self.normalized_names[*i]);
+            // the remainder is cut from the name by characters
+            normalized.starts_with(query) && normalized.chars().count() == name.chars().count()
+        };
+        if query.is_empty() || !matches.first().is_some_and(starts_with_query) {
+            return String::new();
+        }
+        let skip = query.chars().count();
+        let mut candidates = matches
+            .iter()
+            .filter(|i| starts_with_query(i))
+            .map(|i| &self.all_players[*i].name);
+        let mut common: Vec<char> = candidates.next().map_or(Vec::new(), |name| name.chars().skip(skip).collect());
+        for name in candidates {
+            let shared = common.iter().zip(name.chars().skip(skip)).take_while(|(a, b)| **a == *b).count();
+            common.truncate(shared);
+        }
+        common.into_iter().collect()
+    }
+
+    /// The `n` most valuable undrafted players at the selected position,
+    /// regardless of the search input.
+    fn best_available(&self, n: usize) -> Vec<String> {
+        let mut players: Vec<&Player> = self
             .all_players
             .iter()
-            .filter(|p| 
-                p.name.to_ascii_lowercase().contains(&self.input.to_ascii_lowercase()) 
-                && !self.my_players.contains(&p.name) 
+            .filter(|p|
+                !self.my_players.contains(&p.name)
                 && !self.other_players.contains(&p.name)
-                && p.position
-                        .iter()
-                        .any(|x| x.does_position_belong(&self.selected_position))
+                && !self.excluded.contains(&p.name)
+                && p.eligible_for(&self.selected_position)
+                && self.passes_thresholds(p)
             )
-            .take(8)
-            .cloned()
-            .map(|p| p.name)
             .collect();
+        players.sort_by(|a, b| SortMode::PickAvg.compare(a, b));
+        players.into_iter().take(n).map(|p| p.name.clone()).collect()
     }
 
-    fn get_player(&self, name: &String) -> Option<&Player> {
-        self.all_players.iter().find(|p| p.name == *name)
+    /// The `n` most valuable undrafted players, ranking the ones that would
+    /// fill an open positional slot first. Ignores the position filter, the open
+    /// slots decide which positions matter.
+    fn best_available_by_need(&self, n: usize) -> Vec<String> {
+        let open_slots = self.open_slots();
+
+        let mut players: Vec<(bool, &Player)> = self
+            .all_players
+            .iter()
+            .filter(|p|
+                !self.my_players.contains(&p.name)
+                && !self.other_players.contains(&p.name)
+                && !self.excluded.contains(&p.name)
+                && self.passes_thresholds(p)
+            )
+            .map(|p| (self.fills_need(p, &open_slots), p))
+            .collect();
+        players.sort_by(|(a_fills, a), (b_fills, b)| {
+            b_fills.cmp(a_fills).then(SortMode::PickAvg.compare(a, b))
+        });
+        players.into_iter().take(n).map(|(_, p)| p.name.clone()).collect()
+    }
+
+    /// Positions of the roster slots I haven't filled yet, one per slot.
+    fn open_slots(&self) -> Vec<Position> {
+        self.fill_slots(&self.my_players)
+            .into_iter()
+            .filter(|(_, _, _, index)| index.is_none())
+            .map(|(position, _, _, _)| position)
+            .collect()
     }
 
-    fn save_players(&self, players: &Vec<String>, filename: &str) -> Result<(), Box<dyn Error>> {
-        let mut file = File::create(filename)?;
-        let players = players.clone();
-        let json = serde_json::to_string(&players)?;
-        file.write_all(json.as_bytes())?;
+    /// Positions of my open slots the player could fill, each listed once.
+    fn eligible_open_slots(&self, name: &str) -> Vec<Position> {
+        let player = match self.get_player(&name.to_string()) {
+            Some(player) => player,
+            None => return Vec::new(),
+        };
+        let mut eligible: Vec<Position> = Vec::new();
+        for slot in self.open_slots() {
+            if player.eligible_for(&slot) && !eligible.contains(&slot) {
+                eligible.push(slot);
+            }
+        }
+        eligible
+    }
+
+    /// Whether the player would fill one of the open slots. Every player fits
+    /// an ANY, UTIL or BENCH slot, so those don't count as a need.
+    fn fills_need(&self, player: &Player, open_slots: &[Position]) -> bool {
+        open_slots
+            .iter()
+            .filter(|slot| !slot.accepts_anyone())
+            .any(|slot| player.eligible_for(slot))
+    }
+
+    /// Groups the players into tiers of similar value: sorted by average pick,
+    /// a new tier starts whenever the next player goes more than `gap` picks
+    /// later than the previous one.
+    fn compute_tiers(&self, players: &[String], gap: f32) -> Vec<Vec<String>> {
+        let mut sorted: Vec<&Player> = players.iter().filter_map(|p| self.get_player(p)).collect();
+        sorted.sort_by(|a, b| a.pick_avg.total_cmp(&b.pick_avg));
+
+        let mut tiers: Vec<Vec<String>> = Vec::new();
+        let mut last_pick: Option<f32> = None;
+        for player in sorted {
+            match (last_pick, tiers.last_mut()) {
+                (Some(last), Some(tier)) if player.pick_avg - last <= gap => tier.push(player.name.clone()),
+                _ => tiers.push(vec![player.name.clone()]),
+            }
+            last_pick = Some(player.pick_avg);
+        }
+        tiers
+    }
+
+    /// Tier number of every filtered player, see `compute_tiers`.
+    fn tier_numbers(&self) -> HashMap<String, usize> {
+        self.compute_tiers(&self.filtered_players, TIER_GAP)
+            .into_iter()
+            .enumerate()
+            .flat_map(|(i, tier)| tier.into_iter().map(move |name| (name, i + 1)))
+            .collect()
+    }
+
+    /// Number of undrafted players who can play the position, regardless of
+    /// the search and thresholds.
+    fn remaining_at(&self, position: &Position) -> usize {
+        self.all_players
+            .iter()
+            .filter(|p|
+                !self.my_players.contains(&p.name)
+                && !self.other_players.contains(&p.name)
+                && p.eligible_for(position)
+            )
+            .count()
+    }
+
+    fn remaining_count(&self, position: &Position) -> usize {
+        self.remaining
+            .iter()
+            .find(|(p, _)| p == position)
+            .map_or(0, |(_, count)| *count)
+    }
+
+    /// Whether the player is within the draft percentage and pick average
+    /// thresholds, if they are set.
+    fn passes_thresholds(&self, player: &Player) -> bool {
+        self.min_draft_percent.is_none_or(|min| player.draft_percent_value() >= min)
+            && self.max_pick_avg.is_none_or(|max| player.pick_avg <= max)
+    }
+
+    fn start_threshold(&mut self, threshold: Threshold) {
+        let current = match threshold {
+            Threshold::MinDraftPercent => self.min_draft_percent,
+            Threshold::MaxPickAvg => self.max_pick_avg,
+        };
+        self.threshold = threshold;
+        self.threshold_input = current.map(|value| value.to_string()).unwrap_or_default();
+        self.input_mode = InputMode::Threshold;
+    }
+
+    /// Sets the threshold to the typed number, an empty input clears it.
+    fn finish_threshold(&mut self) -> Result<(), String> {
+        let input = std::mem::take(&mut self.threshold_input);
+        let input = input.trim().trim_end_matches('%');
+        let value = if input.is_empty() {
+            None
+        } else {
+            Some(input.parse::<f32>().map_err(|_| format!("{} is not a number", input))?)
+        };
+        match self.threshold {
+            Threshold::MinDraftPercent => self.min_draft_percent = value,
+            Threshold::MaxPickAvg => self.max_pick_avg = value,
+        }
+        self.filter_players();
         Ok(())
     }
 
-    pub fn slots() -> Vec<(Position, u16)> {
-        vec![
-            (Position::C, 3),
-            (Position::PF, 1),
-            (Position::PG, 1),
-            (Position::SG, 1),
-            (Position::SF, 1),
-            (Position::G, 1),
-            (Position::F, 1),
-            (Position::ANY, 7),
-        ]
+    /// Active thresholds for the help bar, e.g. "draft % >= 50, pick avg <= 80"
+    fn threshold_summary(&self) -> Option<String> {
+        let mut parts = Vec::new();
+        if let Some(min) = self.min_draft_percent {
+            parts.push(format!("draft % >= {}", min));
+        }
+        if let Some(max) = self.max_pick_avg {
+            parts.push(format!("pick avg <= {}", max));
+        }
+        if parts.is_empty() {
+            None
+        } else {
+            Some(parts.join(", "))
+        }
     }
 
-}
+    /// Remembers the query that led to the current pick.
+    fn remember_query(&mut self) {
+        let query = std::mem::take(&mut self.typed_query);
+        if !query.is_empty() {
+            self.query_history.retain(|q| *q != query);
+            self.query_history.push(query);
+        }
+        self.history_index = None;
+    }
 
-fn main() -> Result<(), Box<dyn Error>> {
-    // setup terminal
-    enable_raw_mode()?;
-    let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
-    let backend = CrosstermBackend::new(stdout);
-    let mut terminal = Terminal::new(backend)?;
+    /// Replaces the input with the previous query in the history, like the up
+    /// arrow in a shell.
+    fn recall_previous_query(&mut self) {
+        if self.query_history.is_empty() {
+            return;
+        }
+        let index = match self.history_index {
+            Some(index) => index.saturating_sub(1),
+            None => self.query_history.len() - 1,
+        };
+        self.history_index = Some(index);
+        self.input = self.query_history[index].clone();
+        self.typed_query = self.input.clone();
+        self.filter_players();
+    }
 
-    // load players
-    let file = File::open("data.json")?;
-    
-    // use seerde_json to deserialize the JSON data
-    let players: Vec<Player> = serde_json::from_reader(file)?;
-    
-    // create app and run it
-    let mut app = App::default();
+    fn select_previous(&mut self) {
+        if let Some(selected) = self.selected_player {
+            if selected > 0 {
+                self.selected_player = Some(selected - 1);
+            }
+        }
+        self.scroll_to_selected();
+    }
 
-    app.all_players = Vec::new();
-    for player in players {
-        app.all_players.push(player);
+    fn select_next(&mut self) {
+        if let Some(selected) = self.selected_player {
+            if selected + 1 < self.filtered_players.len() {
+                self.selected_player = Some(selected + 1);
+            }
+        } else if !self.filtered_players.is_empty() {
+            self.selected_player = Some(0);
+        }
+        self.scroll_to_selected();
     }
 
-    let args: Vec<String> = env::args().collect();
-    if args.len() > 1 {
-        if args[1] == "load" {
-            // check if my_players.json exists
-            let my_players_file = File::open("my_players.json");
-            if let Ok(file) = my_players_file {
-                let my_players: Vec<String> = serde_json::from_reader(file)?;
-                app.my_players = my_players;
+    fn select_first(&mut self) {
+        if !self.filtered_players.is_empty() {
+            self.selected_player = Some(0);
+        }
+        self.scroll_to_selected();
+    }
+
+    fn select_last(&mut self) {
+        if !self.filtered_players.is_empty() {
+            self.selected_player = Some(self.filtered_players.len() - 1);
+        }
+        self.scroll_to_selected();
+    }
+
+    /// Selects the clicked player in the list, or starts picking them if they
+    /// were already selected. The wheel moves the selection.
+    fn handle_mouse(&mut self, mouse: MouseEvent) {
+        if !matches!(self.input_mode, InputMode::Searching | InputMode::Recommending) {
+            return;
+        }
+        match mouse.kind {
+            MouseEventKind::Down(MouseButton::Left) => {
+                let area = self.list_area;
+                // rows inside the list borders
+                if mouse.column <= area.x
+                    || mouse.column + 1 >= area.x + area.width
+                    || mouse.row <= area.y
+                    || mouse.row + 1 >= area.y + area.height
+                {
+                    return;
+                }
+                let row = (mouse.row - area.y - 1) as usize;
+                let index = self.scroll_offset + row;
+                if row >= self.result_limit || index >= self.filtered_players.len() {
+                    return;
+                }
+                if self.selected_player == Some(index) && self.show_drafted && self.input_mode == InputMode::Searching {
+                    self.start_removing(self.filtered_players[index].clone());
+                } else if self.selected_player == Some(index) {
+                    self.start_picking(self.filtered_players[index].clone());
+                } else {
+                    self.selected_player = Some(index);
+                }
             }
+            MouseEventKind::ScrollUp => self.select_previous(),
+            MouseEventKind::ScrollDown => self.select_next(),
+            _ => {}
+        }
+    }
 
-            let other_players_file = File::open("other_players.json");
-            if let Ok(file) = other_players_file {
-                let other_players: Vec<String> = serde_json::from_reader(file)?;
-                app.other_players = other_players;
+    /// Moves the list viewport so that the selected player is visible.
+    fn scroll_to_selected(&mut self) {
+        let height = self.result_limit.max(1);
+        match self.selected_player {
+            Some(selected) if selected < self.scroll_offset => self.scroll_offset = selected,
+            Some(selected) if selected >= self.scroll_offset + height => {
+                self.scroll_offset = selected + 1 - height
             }
-        } else if args[1] == "delete" {
-            let my_players_file = File::open("my_players.json");
-            if let Ok(_) = my_players_file {
-                std::fs::remove_file("my_players.json")?;
+            _ => {}
+        }
+    }
+
+    /// Selects the first listed player whose name starts with the letter,
+    /// ignoring case and accents.
+    fn jump_to_letter(&mut self, c: char) {
+        let letter = normalize(&c.to_string());
+        if let Some(index) = self
+            .filtered_players
+            .iter()
+            .position(|name| normalize(name).starts_with(&letter))
+        {
+            self.selected_player = Some(index);
+            self.scroll_to_selected();
+        }
+    }
+
+    /// Shows more or fewer players in the list, never less than one.
+    fn change_result_limit(&mut self, delta: isize) {
+        let limit = self.result_limit.saturating_add_signed(delta).max(1);
+        self.manual_result_limit = Some(limit);
+        self.result_limit = limit;
+        self.scroll_to_selected();
+    }
+
+    /// Keeps the selection and the viewport inside the list, e.g. after the
+    /// terminal was resized or the list was filtered.
+    fn clamp_selection(&mut self) {
+        let len = self.filtered_players.len();
+        self.selected_player = match self.selected_player {
+            Some(_) if len == 0 => None,
+            Some(selected) => Some(selected.min(len - 1)),
+            None => None,
+        };
+        self.scroll_offset = self.scroll_offset.min(len.saturating_sub(self.result_limit.max(1)));
+        self.scroll_to_selected();
+    }
+
+    /// Fuzzy matches `query` against `name` the way fzf does: every character of
+    /// the query must appear in the name in order, but not necessarily next to
+    /// each other. Consecutive matches and matches at the start of a word score
+    /// higher, gaps between matches score lower. Returns `None` if the query is
+    /// not a subsequence of the name. Both must already be normalized, so
+    /// matching ignores case and accents. Also returns the indices of the
+    /// matched characters of the name.
+    fn fuzzy_score(&self, query: &str, name: &str) -> Option<(i32, Vec<usize>)> {
+        let name: Vec<char> = name.chars().collect();
+        let mut matched = Vec::new();
+        let mut score = 0;
+        let mut next = 0;
+        let mut last_match: Option<usize> = None;
+
+        for q in query.chars() {
+            let found = (next..name.len()).find(|&i| name[i] == q)?;
+
+            score += 1;
+            if found == 0 || name[found - 1] == ' ' {
+                score += 3;
             }
-            let my_players_file = File::open("other_players.json");
-            if let Ok(_) = my_players_file {
-                std::fs::remove_file("other_players.json")?;
+            match last_match {
+                Some(last) if found == last + 1 => score += 5,
+                Some(last) => score -= (found - last - 1).min(3) as i32,
+                None => score -= found.min(3) as i32,
             }
+
+            last_match = Some(found);
+            matched.push(found);
+            next = found + 1;
         }
+
+        Some((score, matched))
     }
 
-    let res = run_app(&mut terminal, app);
+    fn get_player(&self, name: &String) -> Option<&Player> {
+        self.all_players.iter().find(|p| p.name == *name)
+    }
 
-    // restore terminal
-    disable_raw_mode()?;
-    execute!(
-        terminal.backend_mut(),
-        LeaveAlternateScreen,
-        DisableMouseCapture
-    )?;
-    terminal.show_cursor()?;
+    /// Saves the value as `filename` in the directory of the draft files, see
+    /// `save_json_to`.
+    fn save_json<T: Serialize + ?Sized>(&self, value: &T, filename: &str) -> Result<(), Box<dyn Error>> {
+        save_json_to(value, &self.dir.join(filename))
+    }
 
-    if let Err(err) = res {
-        println!("{:?}", err)
+    /// Like `save_json`, for files of the current draft profile.
+    fn save_draft_json<T: Serialize + ?Sized>(&self, value: &T, filename: &str) -> Result<(), Box<dyn Error>> {
+        save_json_to(value, &self.draft_dir.join(filename))
     }
 
-    Ok(())
-}
+    /// Saves a team's drafted players along with the round they were picked in.
+    /// Keepers come from their own file, so they are left out and survive
+    /// `delete`.
+    fn save_players(&self, players: &[String], filename: &str) -> Result<(), Box<dyn Error>> {
+        let save_file = SaveFile {
+            version: SAVE_FILE_VERSION,
+            players: players
+                .iter()
+                .filter(|name| !self.keepers.contains(name))
+                .map(|name| PlayerPick {
+                    name: name.clone(),
+                    round: self.rounds.get(name).copied().unwrap_or(0),
+                })
+                .collect(),
+        };
+        self.save_draft_json(&save_file, filename)
+    }
 
-fn run_app<B: Backend>(terminal: &mut Terminal<B>, mut app: App) -> io::Result<()> {
-    loop {
-        terminal.draw(|f| ui(f, &app))?;
-
-        if let Event::Key(key) = event::read()? {
-            if key.code == KeyCode::Right {
-                app.selected_position = match app.selected_position {
-                    Position::ANY => Position::PG,
-                    Position::PG => Position::SG,
-                    Position::SG => Position::SF,
-                    Position::SF => Position::PF,
-                    Position::PF => Position::C,
-                    Position::C => Position::F,
-                    Position::F => Position::G,
-                    Position::G => Position::TALL,
-                    Position::TALL => Position::SHORT,
-                    Position::SHORT => Position::ANY,
-                };
-                app.filter_players();
-            } else if key.code == KeyCode::Left {
-                app.selected_position = match app.selected_position {
-                    Position::ANY => Position::SHORT,
-                    Position::PG => Position::ANY,
-                    Position::SG => Position::PG,
-                    Position::SF => Position::SG,
-                    Position::PF => Position::SF,
-                    Position::C => Position::PF,
-                    Position::F => Position::C,
-                    Position::G => Position::F,
-                    Position::TALL => Position::G,
-                    Position::SHORT => Position::TALL,
-                };
-                app.filter_players();
+    /// Drafts the candidate player to my team, or to the other team.
+    fn draft_candidate(&mut self, mine: bool) -> Result<(), Box<dyn Error>> {
+        let name = self.candidate_player.clone();
+        if self.my_players.contains(&name) || self.other_players.contains(&name) {
+            return Err(format!("{} is already drafted", name).into());
+        }
+        self.rounds.insert(name.clone(), self.draft.round() as u16);
+        self.draft.pick(name.clone());
+        if self.recent_picks.len() == RECENT_PICKS_LIMIT {
+            self.recent_picks.pop_front();
+        }
+        self.recent_picks.push_back(recent_pick(&name, mine));
+        if mine {
+            self.my_players.push(name.clone());
+            self.record(DraftAction::Mine(name));
+            self.save_players(&self.my_players, "my_players.json")?;
+        } else {
+            self.other_players.push(name.clone());
+            self.record(DraftAction::Other(name));
+            self.save_players(&self.other_players, "other_players.json")?;
+        }
+        self.start_clock();
+        self.save_session()
+    }
+
+    /// Restarts the draft clock for the next pick, if the draft is timed.
+    fn start_clock(&mut self) {
+        self.pick_deadline = self.pick_clock.map(|clock| Instant::now() + clock);
+    }
+
+    /// Seconds left for the current pick, if the draft is timed.
+    fn clock_secs_left(&self) -> Option<u64> {
+        self.pick_deadline
+            .map(|deadline| deadline.saturating_duration_since(Instant::now()).as_secs())
+    }
+
+    /// Drafts the best available player for my needs once the clock runs out
+    /// on my pick, abandoning any pick in progress. Returns the drafted player.
+    /// Any other team's clock, or mine without auto pick, just stops.
+    fn expire_clock(&mut self) -> Result<Option<String>, Box<dyn Error>> {
+        if self.pick_deadline.is_none_or(|deadline| deadline > Instant::now()) {
+            return Ok(None);
+        }
+        let team = self.draft.on_the_clock();
+        if !self.auto_pick || self.my_slot != Some(team) {
+            self.pick_deadline = None;
+            self.status_message = Some(format!("Time's up for team {}", team + 1));
+            return Ok(None);
+        }
+        let name = match self.best_available_by_need(1).pop() {
+            Some(name) if !self.at_capacity() => name,
+            // nothing sensible to draft, stop the clock instead of retrying
+            _ => {
+                self.pick_deadline = None;
+                return Ok(None);
             }
-            match app.input_mode {
-                InputMode::Idle => match key.code {
-                    KeyCode::Char('s') | KeyCode::Enter | KeyCode::Up | KeyCode::Down => {
-                        app.input_mode = InputMode::Searching;
-                        app.filter_players();
-                    }
-                    KeyCode::Char('q') => {
+        };
+        if matches!(self.input_mode, InputMode::Picking | InputMode::Noting) {
+            self.input.clear();
+            self.input_mode = self.pick_return_mode;
+        }
+        self.draft_now(&name)?;
+        Ok(Some(name))
+    }
+
+    /// Drafts the best available player at the selected position to my team
+    /// without going through picking. Returns the drafted player, `None` if
+    /// there is nobody left or my roster is full.
+    fn draft_top_available(&mut self) -> Result<Option<String>, Box<dyn Error>> {
+        if self.at_capacity() {
+            return Ok(None);
+        }
+        match self.best_available(1).pop() {
+            Some(name) => {
+                self.draft_now(&name)?;
+                Ok(Some(name))
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// Drafts the player to my team right away and refreshes the list.
+    fn draft_now(&mut self, name: &str) -> Result<(), Box<dyn Error>> {
+        self.candidate_player = name.to_string();
+        self.draft_candidate(true)?;
+        self.candidate_player.clear();
+        self.selected_player = None;
+        self.filter_players();
+        Ok(())
+    }
+
+    /// Roster positions with empty slots, and how many are empty.
+    fn validate_roster(&self) -> Vec<(Position, u16)> {
+        self.slot_fill()
+            .into_iter()
+            .filter(|(_, filled, total)| filled < total)
+            .map(|(position, filled, total)| (position, total - filled))
+            .collect()
+    }
+
+    /// Exports my roster and describes the outcome for the status line.
+    fn export(&self, export: Export) -> String {
+        let filename = export.filename();
+        let result = match export {
+            Export::Csv => self.export_csv(filename),
+            Export::Markdown => self.export_markdown(filename),
+        };
+        let skipped = self.my_players.iter().filter(|p| self.get_player(p).is_none()).count();
+        match result {
+            Ok(()) if export == Export::Csv && skipped > 0 => {
+                format!("Exported to {}, skipped {} unknown players", filename, skipped)
+            }
+            Ok(()) => format!("Exported to {}", filename),
+            Err(err) => format!("Export failed: {}", err),
+        }
+    }
+
+    /// Starts the export, or asks first if the roster has empty slots.
+    fn start_export(&mut self, export: Export) {
+        let empty: u16 = self.validate_roster().iter().map(|(_, count)| count).sum();
+        if empty > 0 {
+            self.pending_export = Some(export);
+            self.status_message = Some(format!(
+                "Roster has {} empty slot{} \u{2014} export anyway? (y/n)",
+                empty,
+                if empty == 1 { "" } else { "s" }
+            ));
+        } else {
+            self.status_message = Some(self.export(export));
+        }
+    }
+
+    /// Writes my players with their stats to a CSV file, followed by the
+    /// positions with empty slots. Players missing from the dataset are skipped.
+    fn export_csv(&self, filename: &str) -> Result<(), Box<dyn Error>> {
+        let mut file = File::create(self.draft_dir.join(filename))?;
+        writeln!(file, "name,team,position,pick_avg,round_avg,draft_percent")?;
+        for player in self.my_players.iter().filter_map(|p| self.get_player(p)) {
+            let position = player
+                .position
+                .iter()
+                .map(|p| format!("{:?}", p))
+                .collect::<Vec<String>>()
+                .join("|");
+            writeln!(
+                file,
+                "{},{},{},{},{},{}",
+                csv_field(&player.name),
+                csv_field(&player.team),
+                position,
+                player.pick_avg,
+                player.round_avg,
+                csv_field(&player.draft_percent)
+            )?;
+        }
+        let needs = self.validate_roster();
+        if !needs.is_empty() {
+            writeln!(file)?;
+            writeln!(file, "need,empty_slots")?;
+            for (position, count) in needs {
+                writeln!(file, "{:?},{}", position, count)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Writes a shareable summary of my draft as GitHub-flavored Markdown:
+    /// the roster by slot, the total pick value, the NBA teams and the open slots.
+    fn export_markdown(&self, filename: &str) -> Result<(), Box<dyn Error>> {
+        let slots = self.fill_slots(&self.my_players);
+        let mut file = File::create(self.draft_dir.join(filename))?;
+
+        writeln!(file, "# Draft summary")?;
+        writeln!(file)?;
+        writeln!(file, "| Slot | Player | Team | Position | Pick avg |")?;
+        writeln!(file, "| --- | --- | --- | --- | ---: |")?;
+        for (slot, name, _, _) in slots.iter().filter(|(_, _, _, index)| index.is_some()) {
+            if let Some(player) = self.get_player(name) {
+                writeln!(
+                    file,
+                    "| {:?} | {} | {} | {} | {:.1} |",
+                    slot,
+                    markdown_cell(&player.name),
+                    markdown_cell(&player.team),
+                    player.position_label(),
+                    player.pick_avg
+                )?;
+            }
+        }
+        writeln!(file)?;
+
+        let value: f32 = self
+            .my_players
+            .iter()
+            .filter_map(|p| self.get_player(p))
+            .map(|p| p.pick_avg)
+            .sum();
+        writeln!(file, "**Total pick value:** {:.1}", value)?;
+        writeln!(file)?;
+
+        writeln!(file, "## NBA teams")?;
+        writeln!(file)?;
+        for (team, count) in self.team_distribution() {
+            writeln!(file, "- {}: {}", markdown_cell(&team), count)?;
+        }
+        writeln!(file)?;
+
+        writeln!(file, "## Needs")?;
+        writeln!(file)?;
+        let needs = self.validate_roster();
+        if needs.is_empty() {
+            writeln!(file, "None, every slot is filled.")?;
+        } else {
+            for (position, count) in needs {
+                writeln!(file, "- {:?}: {} empty", position, count)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn save_session(&self) -> Result<(), Box<dyn Error>> {
+        let session = Session {
+            selected_position: self.selected_position.clone(),
+            sort_mode: Some(self.sort_mode),
+            display_stat: self.display_stat,
+            min_draft_percent: self.min_draft_percent,
+            max_pick_avg: self.max_pick_avg,
+            picks: self.draft.picks.iter().map(|(_, name)| name.clone()).collect(),
+        };
+        self.save_draft_json(&session, "session.json")
+    }
+
+    /// Notes are kept apart from the draft files so they survive `delete`.
+    fn save_notes(&self) -> Result<(), Box<dyn Error>> {
+        self.save_json(&self.notes, "notes.json")
+    }
+
+    /// Starts typing a note for the candidate player, prefilled with the
+    /// existing one.
+    fn start_noting(&mut self) {
+        self.note_input = self.notes.get(&self.candidate_player).cloned().unwrap_or_default();
+        self.input_mode = InputMode::Noting;
+    }
+
+    /// Saves the typed note for the candidate player, an empty note removes it.
+    fn finish_noting(&mut self) -> Result<(), Box<dyn Error>> {
+        let note = std::mem::take(&mut self.note_input);
+        if note.trim().is_empty() {
+            self.notes.remove(&self.candidate_player);
+        } else {
+            self.notes.insert(self.candidate_player.clone(), note.trim().to_string());
+        }
+        self.input_mode = InputMode::Picking;
+        self.save_notes()
+    }
+
+    /// Adds the player to the watchlist, or removes them if they are on it.
+    fn toggle_watchlist(&mut self, name: String) -> Result<(), Box<dyn Error>> {
+        if self.watchlist.contains(&name) {
+            self.watchlist.retain(|p| *p != name);
+        } else {
+            self.watchlist.push(name);
+        }
+        self.save_json(&self.watchlist, "watchlist.json")
+    }
+
+    /// Hides the player from search, or shows them again if they are hidden.
+    fn toggle_excluded(&mut self, name: String) -> Result<(), Box<dyn Error>> {
+        if self.excluded.contains(&name) {
+            self.excluded.retain(|p| *p != name);
+        } else {
+            self.excluded.push(name);
+        }
+        self.save_json(&self.excluded, "excluded.json")?;
+        self.filter_players();
+        Ok(())
+    }
+
+    /// Number of rows in the listing, to keep the selected one in range
+    fn listing_len(&self) -> usize {
+        if self.show_excluded {
+            self.excluded.len()
+        } else if self.show_watchlist {
+            self.watchlist.len()
+        } else {
+            self.roster_rows().len()
+        }
+    }
+
+    /// Name and roster index of every row of the listed roster: the slots,
+    /// then the players missing from the dataset, who don't fit any slot.
+    /// Empty slots have no index.
+    fn roster_rows(&self) -> Vec<(String, Option<usize>)> {
+        let players = self.listed_players();
+        let mut rows: Vec<(String, Option<usize>)> = self
+            .fill_slots(players)
+            .into_iter()
+            .map(|(_, name, _, index)| (name, index))
+            .collect();
+        rows.extend(
+            players
+                .iter()
+                .enumerate()
+                .filter(|(_, p)| self.get_player(p).is_none())
+                .map(|(index, p)| (p.clone(), Some(index))),
+        );
+        rows
+    }
+
+    /// Roster shown in the listing, mine or the other team's
+    fn listed_players(&self) -> &Vec<String> {
+        if self.list_other_team {
+            &self.other_players
+        } else {
+            &self.my_players
+        }
+    }
+
+    /// Takes a player off the listed roster. `index` is the player's index in it.
+    fn remove_listed_player(&mut self, index: usize) -> Result<(), Box<dyn Error>> {
+        let name = self.listed_players()[index].clone();
+        self.remove_player(&name)?;
+        Ok(())
+    }
+
+    /// Takes a drafted player off whichever team has them and out of the
+    /// draft, putting them back in the pool. Returns whether they were drafted.
+    fn remove_player(&mut self, name: &str) -> Result<bool, Box<dyn Error>> {
+        let mine = self.my_players.iter().any(|p| p == name);
+        if mine {
+            self.my_players.retain(|p| p != name);
+            self.save_players(&self.my_players, "my_players.json")?;
+        } else if self.other_players.iter().any(|p| p == name) {
+            self.other_players.retain(|p| p != name);
+            self.save_players(&self.other_players, "other_players.json")?;
+        } else {
+            return Ok(false);
+        }
+
+        // a later undo must not take back a pick that is already gone
+        self.history
+            .retain(|action| !matches!(action, DraftAction::Mine(n) | DraftAction::Other(n) if n == name));
+        self.recent_picks.retain(|p| *p != recent_pick(name, mine));
+        self.rounds.remove(name);
+        self.draft.remove(name);
+        self.start_clock();
+        self.save_session()?;
+        self.filter_players();
+        Ok(true)
+    }
+
+    /// Asks before taking a drafted player off the draft, since that can't be
+    /// undone.
+    fn start_removing(&mut self, name: String) {
+        self.status_message = Some(format!("Remove {} from the draft? (y/n)", name));
+        self.pending_removal = Some(name);
+    }
+
+    /// Takes a drafted player off whichever team has them, putting them back
+    /// in the pool.
+    fn remove_drafted(&mut self, name: &str) -> Result<(), Box<dyn Error>> {
+        if self.remove_player(name)? {
+            self.status_message = Some(format!("Removed {} from the draft", name));
+        }
+        Ok(())
+    }
+
+    /// Swaps the player at `index` of the listed roster with the one before
+    /// (`up`) or after them, and keeps them selected. Slots are filled in
+    /// roster order, so a player moved up gets first claim on the slots they
+    /// fit, which can move other players to different slots.
+    fn reorder_listed_player(&mut self, index: usize, up: bool) -> Result<(), Box<dyn Error>> {
+        let (players, filename) = if self.list_other_team {
+            (&mut self.other_players, "other_players.json")
+        } else {
+            (&mut self.my_players, "my_players.json")
+        };
+        let other = if up { index.checked_sub(1) } else { Some(index + 1) };
+        let other = match other {
+            Some(other) if other < players.len() => other,
+            _ => return Ok(()),
+        };
+        players.swap(index, other);
+        let name = players[other].clone();
+
+        self.save_players(self.listed_players(), filename)?;
+        if let Some(row) = self.roster_rows().iter().position(|(row_name, _)| *row_name == name) {
+            self.selected_slot = row;
+        }
+        Ok(())
+    }
+
+    /// Moves a drafted player between the teams, e.g. after drafting them to
+    /// the wrong one. `index` is the player's index in the team they leave.
+    fn move_player(&mut self, index: usize, from_mine: bool) -> Result<(), Box<dyn Error>> {
+        let (from, to) = if from_mine {
+            (&mut self.my_players, &mut self.other_players)
+        } else {
+            (&mut self.other_players, &mut self.my_players)
+        };
+        let name = from.remove(index);
+        to.push(name.clone());
+
+        let from_pick = recent_pick(&name, from_mine);
+        for pick in self.recent_picks.iter_mut().filter(|p| **p == from_pick) {
+            *pick = recent_pick(&name, !from_mine);
+        }
+
+        // undoing the pick later has to take the player off the right team
+        for action in self.history.iter_mut() {
+            match action {
+                DraftAction::Mine(n) if from_mine && *n == name => *action = DraftAction::Other(name.clone()),
+                DraftAction::Other(n) if !from_mine && *n == name => *action = DraftAction::Mine(name.clone()),
+                _ => {}
+            }
+        }
+
+        self.save_players(&self.my_players, "my_players.json")?;
+        self.save_players(&self.other_players, "other_players.json")?;
+        self.filter_players();
+        Ok(())
+    }
+
+    /// Copies the player's name to the system clipboard. Without a clipboard,
+    /// e.g. over SSH, the name is written to clipboard.txt in the draft
+    /// directory instead. Returns the confirmation to show.
+    fn copy_name(&mut self, name: &str) -> String {
+        if self.clipboard.is_none() {
+            self.clipboard = arboard::Clipboard::new().ok();
+        }
+        let copied = match self.clipboard.as_mut() {
+            Some(clipboard) => clipboard.set_text(name.to_string()),
+            None => Err(arboard::Error::ClipboardNotSupported),
+        };
+        match copied {
+            Ok(()) => format!("Copied: {}", name),
+            Err(_) => match std::fs::write(self.draft_dir.join("clipboard.txt"), name) {
+                Ok(()) => format!("No clipboard, wrote {} to clipboard.txt", name),
+                Err(err) => format!("Copying {} failed: {}", name, err),
+            },
+        }
+    }
+
+    /// Drafts the candidate player and goes back to where the pick started.
+    fn finish_pick(&mut self, mine: bool) -> Result<(), Box<dyn Error>> {
+        self.draft_candidate(mine)?;
+        self.remember_query();
+        self.candidate_player.clear();
+        self.input.clear();
+        self.input_mode = self.pick_return_mode;
+        self.selected_player = None;
+        self.filter_players();
+        Ok(())
+    }
+
+    /// Adds the candidate to my team unless the roster is already full.
+    fn pick_for_my_team(&mut self) -> Result<(), Box<dyn Error>> {
+        if self.at_capacity() {
+            self.status_message = Some("Roster full — remove a player first, or press O to add anyway".to_string());
+            return Ok(());
+        }
+        self.finish_pick(true)
+    }
+
+    /// Names of the saved picks, remembering the round of every pick that has one.
+    fn restore_picks(&mut self, picks: Vec<PlayerPick>) -> Vec<String> {
+        picks
+            .into_iter()
+            .map(|pick| {
+                if pick.round > 0 {
+                    self.rounds.insert(pick.name.clone(), pick.round);
+                }
+                pick.name
+            })
+            .collect()
+    }
+
+    /// Replays the saved pick order into the draft state after `load`.
+    /// Drafted players missing from it, e.g. from a session saved before the
+    /// order was, follow in the order of the round they were picked in.
+    /// Keepers weren't drafted, so they aren't picks.
+    fn restore_draft(&mut self, saved: Vec<String>) {
+        let drafted: Vec<String> = self
+            .my_players
+            .iter()
+            .chain(self.other_players.iter())
+            .filter(|p| !self.keepers.contains(p))
+            .cloned()
+            .collect();
+        let mut order: Vec<String> = Vec::new();
+        for name in saved {
+            if drafted.contains(&name) && !order.contains(&name) {
+                order.push(name);
+            }
+        }
+        let mut rest: Vec<String> = drafted.into_iter().filter(|p| !order.contains(p)).collect();
+        // unknown rounds go last
+        rest.sort_by_key(|p| self.rounds.get(p).copied().unwrap_or(u16::MAX));
+        order.extend(rest);
+
+        self.draft = DraftState::new(self.draft.num_teams);
+        for name in order {
+            self.draft.pick(name);
+        }
+    }
+
+    fn record(&mut self, action: DraftAction) {
+        if self.history.len() == HISTORY_LIMIT {
+            self.history.remove(0);
+        }
+        self.history.push(action);
+    }
+
+    /// Reverts the most recent draft action, if there is one.
+    fn undo(&mut self) -> Result<(), Box<dyn Error>> {
+        match self.history.pop() {
+            Some(DraftAction::Mine(name)) => {
+                self.my_players.retain(|p| *p != name);
+                self.rounds.remove(&name);
+                self.recent_picks.retain(|p| *p != recent_pick(&name, true));
+                self.draft.remove(&name);
+            }
+            Some(DraftAction::Other(name)) => {
+                self.other_players.retain(|p| *p != name);
+                self.rounds.remove(&name);
+                self.recent_picks.retain(|p| *p != recent_pick(&name, false));
+                self.draft.remove(&name);
+            }
+            None => return Ok(()),
+        }
+        self.start_clock();
+        self.save_players(&self.my_players, "my_players.json")?;
+        self.save_players(&self.other_players, "other_players.json")?;
+        self.save_session()?;
+        self.filter_players();
+        Ok(())
+    }
+
+    /// Removes players that are on both teams from the other team, keeping them
+    /// on mine. Returns the names of the conflicting players.
+    fn reconcile_rosters(&mut self) -> Vec<String> {
+        let conflicts: Vec<String> = self
+            .other_players
+            .iter()
+            .filter(|p| self.my_players.contains(p))
+            .cloned()
+            .collect();
+        self.other_players.retain(|p| !conflicts.contains(p));
+        conflicts
+    }
+
+    /// Puts the keepers at the top of their rosters, unless they are there
+    /// already. My keepers are taken off the other roster if a saved draft
+    /// has them there.
+    fn add_keepers(&mut self, mine: &[String], other: &[String]) {
+        let mut kept = Vec::new();
+        for name in mine.iter() {
+            self.other_players.retain(|p| p != name);
+            if !self.my_players.contains(name) && !kept.contains(name) {
+                kept.push(name.clone());
+            }
+        }
+        self.my_players.splice(0..0, kept);
+
+        let mut kept = Vec::new();
+        for name in other.iter() {
+            if !self.my_players.contains(name) && !self.other_players.contains(name) && !kept.contains(name) {
+                kept.push(name.clone());
+            }
+        }
+        self.other_players.splice(0..0, kept);
+    }
+
+    /// Reads the players again from the path or URL they came from, for when
+    /// the dataset changes during the draft, and returns how many there are
+    /// now. Drafted players missing from the new data stay on their roster,
+    /// like with `load`. The current players are kept if nothing could be read.
+    fn reload_dataset(&mut self, path: &str) -> Result<usize, Box<dyn Error>> {
+        let (players, errors) = if path.starts_with("http://") || path.starts_with("https://") {
+            fetch_players(path)?
+        } else if self.csv_source {
+            load_players_csv(path)?
+        } else {
+            load_players(path)
+        };
+        if players.is_empty() {
+            return Err(errors.first().cloned().unwrap_or(format!("{} has no players", path)).into());
+        }
+
+        self.has_ranks = players.iter().any(|p| p.rank.is_some());
+        if self.sort_mode == SortMode::Rank && !self.has_ranks {
+            self.sort_mode = SortMode::default_for(false);
+        }
+        self.normalized_names = players.iter().map(|p| normalize(&p.name)).collect();
+        self.all_players = players;
+        // the cached matches are indices into the old players
+        self.last_candidates = None;
+
+        let missing = self.validate_against_dataset();
+        let mut message = format!("Reloaded {} players from {}", self.all_players.len(), path);
+        if !errors.is_empty() {
+            message.push_str(&format!(", skipped {} malformed", errors.len()));
+        }
+        if !missing.is_empty() {
+            message.push_str(&format!(", no longer in the data: {}", missing.join(", ")));
+        }
+        self.status_message = Some(message);
+        self.filter_players();
+        Ok(self.all_players.len())
+    }
+
+    /// Drafted players that don't exist in the loaded dataset, e.g. because the
+    /// saved draft files were created with an older dataset.
+    fn validate_against_dataset(&self) -> Vec<String> {
+        self.my_players
+            .iter()
+            .chain(self.other_players.iter())
+            .filter(|p| self.get_player(p).is_none())
+            .cloned()
+            .collect()
+    }
+
+    /// Assigns the given players to the roster slots in draft order. Each row
+    /// holds the slot position, the player's name (or "Empty"), the player's
+    /// positions and the index of the player in `players`, if the slot is filled.
+    /// BENCH slots are filled last, and players that don't fit anywhere are
+    /// added to the bench on top of its slots rather than left out.
+    fn fill_slots(&self, players: &[String]) -> Vec<(Position, String, Vec<Position>, Option<usize>)> {
+        let mut filled_slots: Vec<(Position, String, Vec<Position>, Option<usize>)> = Vec::new();
+
+        let mut slots = self.slots();
+        slots.sort_by_key(|(position, _)| *position == Position::BENCH);
+        for (position, slot) in slots.iter() {
+            let mut slots_left = *slot;
+            for (index, player) in players.iter().enumerate() {
+                // players missing from the dataset can't be placed, see validate_against_dataset
+                let player: &Player = match self.get_player(player) {
+                    Some(player) => player,
+                    None => continue,
+                };
+                if  !filled_slots.iter().any(|x| x.1 == player.name) &&
+                    player.eligible_for(position) &&
+                    slots_left > 0 {
+                    filled_slots.push((position.clone(), player.name.clone(), player.position.clone(), Some(index)));
+                    slots_left -= 1;
+                }
+                if slots_left == 0 {
+                    break;
+                }
+            }
+            while slots_left > 0 {
+                filled_slots.push((position.clone(), "Empty".to_string(), vec![], None));
+                slots_left -= 1;
+            }
+        }
+
+        for (index, player) in players.iter().enumerate() {
+            if let Some(player) = self.get_player(player) {
+                if !filled_slots.iter().any(|x| x.1 == player.name) {
+                    filled_slots.push((Position::BENCH, player.name.clone(), player.position.clone(), Some(index)));
+                }
+            }
+        }
+
+        filled_slots
+    }
+
+    /// Row of the roster slot the player would take if I picked them now, or
+    /// `None` if none of their slots is open.
+    fn preview_slot(&self, name: &str) -> Option<usize> {
+        let mut players = self.my_players.clone();
+        players.push(name.to_string());
+        let candidate = players.len() - 1;
+        self.fill_slots(&players)
+            .iter()
+            .position(|(_, _, _, index)| *index == Some(candidate))
+    }
+
+    /// Number of filled slots and total slots for every roster position.
+    fn slot_fill(&self) -> Vec<(Position, u16, u16)> {
+        let filled_slots = self.fill_slots(&self.my_players);
+        self.slots()
+            .into_iter()
+            .map(|(position, total)| {
+                let filled = filled_slots
+                    .iter()
+                    .filter(|(slot, _, _, index)| *slot == position && index.is_some())
+                    .count() as u16;
+                (position, filled, total)
+            })
+            .collect()
+    }
+
+    /// Position of the slots with the largest unfilled share, preferring
+    /// specific positions over the ones anyone fits. `Position::ANY` if the
+    /// roster is full.
+    fn most_needed_position(&self) -> Position {
+        let open: Vec<(Position, f32)> = self
+            .slot_fill()
+            .into_iter()
+            .filter(|(_, filled, total)| filled < total)
+            .map(|(position, filled, total)| (position, (total - filled) as f32 / total as f32))
+            .collect();
+        let needs_specific = open.iter().any(|(position, _)| !position.accepts_anyone());
+        open.into_iter()
+            .filter(|(position, _)| !needs_specific || !position.accepts_anyone())
+            .reduce(|best, next| if next.1 > best.1 { next } else { best })
+            .map_or(Position::ANY, |(position, _)| position)
+    }
+
+    /// Players on my team that play for the player's NBA team
+    fn teammates_drafted(&self, player: &Player) -> Vec<&Player> {
+        self.my_players
+            .iter()
+            .filter_map(|name| self.get_player(name))
+            .filter(|p| p.name != player.name && p.team.eq_ignore_ascii_case(&player.team))
+            .collect()
+    }
+
+    /// Count, average pick and primary positions of the players nobody has
+    /// drafted yet.
+    fn pool_stats(&self) -> PoolStats {
+        let pool: Vec<&Player> = self
+            .all_players
+            .iter()
+            .filter(|p| !self.my_players.contains(&p.name) && !self.other_players.contains(&p.name))
+            .collect();
+        let avg_pick = if pool.is_empty() {
+            0.0
+        } else {
+            pool.iter().map(|p| p.pick_avg).sum::<f32>() / pool.len() as f32
+        };
+        let by_position = [Position::PG, Position::SG, Position::SF, Position::PF, Position::C]
+            .into_iter()
+            .map(|position| {
+                let count = pool.iter().filter(|p| p.position.first() == Some(&position)).count();
+                (position, count)
+            })
+            .collect();
+        PoolStats {
+            count: pool.len(),
+            avg_pick,
+            by_position,
+        }
+    }
+
+    /// Warnings about the balance of my roster: positions with more players
+    /// than the slots they fit, and, late in the draft, positions I haven't
+    /// drafted anyone for yet. Slots anyone fits are ignored.
+    fn roster_warnings(&self) -> Vec<String> {
+        let mut warnings = Vec::new();
+        let slots = self.slots();
+        let capacity: u16 = slots.iter().map(|(_, count)| count).sum();
+        let late = self.my_players.len() as f32 >= capacity as f32 * LATE_DRAFT_SHARE;
+
+        if late {
+            for (position, filled, _) in self.slot_fill() {
+                if filled == 0 && !position.accepts_anyone() {
+                    warnings.push(format!("No {:?} drafted yet", position));
+                }
+            }
+        }
+
+        for position in [Position::PG, Position::SG, Position::SF, Position::PF, Position::C] {
+            let drafted = self
+                .my_players
+                .iter()
+                .filter_map(|p| self.get_player(p))
+                .filter(|p| p.position.first() == Some(&position))
+                .count();
+            let fitting: u16 = slots
+                .iter()
+                .filter(|(slot, _)| !slot.accepts_anyone() && position.does_position_belong(slot))
+                .map(|(_, count)| count)
+                .sum();
+            if drafted > fitting as usize {
+                warnings.push(format!("{} {:?}s for {} slots", drafted, position, fitting));
+            }
+        }
+
+        warnings
+    }
+
+    /// Whether my team already has as many players as there are slots. This
+    /// is what limits drafting; see `slots_all_filled` for the roster shape.
+    fn at_capacity(&self) -> bool {
+        let capacity: usize = self.slots().iter().map(|(_, count)| *count as usize).sum();
+        self.my_players.len() >= capacity
+    }
+
+    /// Whether every slot holds an eligible player. Unlike `at_capacity` this
+    /// stays false while players that don't fit an open slot sit on the extra
+    /// bench, since a position is still needed then.
+    fn slots_all_filled(&self) -> bool {
+        self.slot_fill().iter().all(|(_, filled, total)| filled >= total)
+    }
+
+    /// How many of my players play for each NBA team, most first. Players
+    /// missing from the dataset are skipped.
+    fn team_distribution(&self) -> Vec<(String, usize)> {
+        let mut counts: HashMap<String, usize> = HashMap::new();
+        for player in self.my_players.iter().filter_map(|p| self.get_player(p)) {
+            *counts.entry(player.team.clone()).or_insert(0) += 1;
+        }
+        let mut distribution: Vec<(String, usize)> = counts.into_iter().collect();
+        distribution.sort_by(|(a_team, a), (b_team, b)| b.cmp(a).then(a_team.cmp(b_team)));
+        distribution
+    }
+
+    /// Number of picks made so far by all teams
+    fn picks_made(&self) -> usize {
+        self.draft.current_pick
+    }
+
+    /// Green if the player usually goes well before the current pick (a value),
+    /// red if they usually go well after it (a reach).
+    fn adp_color(&self, player: &Player) -> Color {
+        let current_pick = (self.picks_made() + 1) as f32;
+        if player.pick_avg + ADP_THRESHOLD < current_pick {
+            Color::Green
+        } else if player.pick_avg - ADP_THRESHOLD > current_pick {
+            Color::Red
+        } else {
+            Color::Reset
+        }
+    }
+
+    /// What `adp_color` says, for when colors aren't seen
+    fn adp_label(&self, player: &Player) -> &'static str {
+        match self.adp_color(player) {
+            Color::Green => "[VALUE] ",
+            Color::Red => "[REACH] ",
+            _ => "",
+        }
+    }
+
+    fn start_picking(&mut self, name: String) {
+        self.candidate_player = name;
+        self.pick_return_mode = self.input_mode;
+        self.input_mode = InputMode::Picking;
+    }
+
+    /// Changes the position filter, remembering the previous one.
+    fn set_position(&mut self, position: Position) {
+        self.prev_position = std::mem::replace(&mut self.selected_position, position);
+        self.filter_players();
+    }
+
+    /// Resets the search, the position, the thresholds and the sorting, for
+    /// when stacked filters leave nothing to pick from.
+    fn clear_filters(&mut self) {
+        self.input.clear();
+        self.typed_query.clear();
+        self.history_index = None;
+        self.selected_player = None;
+        self.prev_position = std::mem::replace(&mut self.selected_position, Position::ANY);
+        self.min_draft_percent = None;
+        self.max_pick_avg = None;
+        self.sort_mode = SortMode::default_for(self.has_ranks);
+        self.show_drafted = false;
+        self.status_message = Some("Filters cleared".to_string());
+        self.filter_players();
+    }
+
+    /// Swaps back to the previous position filter, so pressing it again
+    /// toggles between the two.
+    fn swap_to_previous_position(&mut self) {
+        let previous = self.prev_position.clone();
+        self.set_position(previous);
+    }
+
+    fn start_position_jump(&mut self) {
+        self.jump_keys.clear();
+        self.jump_return_mode = self.input_mode;
+        self.input_mode = InputMode::PositionJump;
+    }
+
+    /// Sum of the average draft picks of the given players, skipping players
+    /// missing from the dataset.
+    fn pick_value(&self, players: &[String]) -> f32 {
+        players
+            .iter()
+            .filter_map(|p| self.get_player(p))
+            .map(|p| p.pick_avg)
+            .sum()
+    }
+
+    fn roster_pick_value(&self) -> f32 {
+        self.pick_value(&self.my_players)
+    }
+
+    pub fn slots(&self) -> Vec<(Position, u16)> {
+        self.slots.clone()
+    }
+
+    /// Roster shape used when there is no slots.json
+    pub fn default_slots() -> Vec<(Position, u16)> {
+        vec![
+            (Position::C, 3),
+            (Position::PF, 1),
+            (Position::PG, 1),
+            (Position::SG, 1),
+            (Position::SF, 1),
+            (Position::G, 1),
+            (Position::F, 1),
+            (Position::ANY, 7),
+        ]
+    }
+
+}
+
+/// Command line arguments
+struct Args {
+    /// Positional command, either `load` or `delete`
+    command: Option<String>,
+    /// Path of the player dataset
+    data_path: String,
+    /// URL to fetch the player dataset from, before falling back to `data_path`
+    url: Option<String>,
+    /// Path of a player dataset in CSV, used instead of `data_path`
+    csv: Option<String>,
+    /// Directory holding the saved draft files
+    dir: PathBuf,
+    /// Number of teams in the draft
+    teams: usize,
+    /// Skip the confirmation prompt of `delete`
+    force: bool,
+    /// Saved draft file of another roster to show next to mine
+    compare: Option<PathBuf>,
+    /// Name of the draft profile, whose files live in `drafts/<name>`
+    profile: Option<String>,
+    /// Print the draft profiles instead of starting
+    list_profiles: bool,
+    /// Seconds every team has for a pick
+    clock: Option<u64>,
+    /// Draft the best available player for me when the clock runs out
+    auto_pick: bool,
+    /// My team's place in the draft order, starting from 1
+    slot: Option<usize>,
+    /// Label what colors say, see `App::accessible`
+    accessible: bool,
+    /// File of the players kept on a roster before the draft
+    keepers: Option<PathBuf>,
+}
+
+fn parse_args(args: &[String]) -> Result<Args, String> {
+    let mut parsed = Args {
+        command: None,
+        data_path: "data.json".to_string(),
+        url: None,
+        csv: None,
+        dir: PathBuf::from("."),
+        teams: 12,
+        force: false,
+        compare: None,
+        profile: None,
+        list_profiles: false,
+        clock: None,
+        auto_pick: false,
+        slot: None,
+        accessible: false,
+        keepers: None,
+    };
+
+    let mut args = args.iter().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--data" => parsed.data_path = args.next().ok_or("--data expects a path")?.clone(),
+            "--url" => parsed.url = Some(args.next().ok_or("--url expects a URL")?.clone()),
+            "--csv" => parsed.csv = Some(args.next().ok_or("--csv expects a path")?.clone()),
+            "--dir" => parsed.dir = PathBuf::from(args.next().ok_or("--dir expects a path")?),
+            "--teams" => {
+                parsed.teams = args
+                    .next()
+                    .and_then(|n| n.parse().ok())
+                    .filter(|n| *n > 0)
+                    .ok_or("--teams expects a positive number")?
+            }
+            "--force" => parsed.force = true,
+            "--clock" => {
+                parsed.clock = Some(
+                    args.next()
+                        .and_then(|n| n.parse().ok())
+                        .filter(|n| *n > 0)
+                        .ok_or("--clock expects a positive number of seconds")?,
+                )
+            }
+            "--auto-pick" => parsed.auto_pick = true,
+            "--slot" => {
+                parsed.slot = Some(
+                    args.next()
+                        .and_then(|n| n.parse().ok())
+                        .filter(|n| *n > 0)
+                        .ok_or("--slot expects a positive number")?,
+                )
+            }
+            "--accessible" => parsed.accessible = true,
+            "--compare" => parsed.compare = Some(PathBuf::from(args.next().ok_or("--compare expects a path")?)),
+            "--keepers" => parsed.keepers = Some(PathBuf::from(args.next().ok_or("--keepers expects a path")?)),
+            "--profile" => {
+                let profile = args.next().ok_or("--profile expects a name")?;
+                if profile.is_empty() || profile.contains(['/', '\\']) || profile.starts_with('.') {
+                    return Err(format!("invalid profile name: {}", profile));
+                }
+                parsed.profile = Some(profile.clone());
+            }
+            "--list-profiles" => parsed.list_profiles = true,
+            "load" | "delete" => parsed.command = Some(arg.clone()),
+            _ => return Err(format!("unknown argument: {}", arg)),
+        }
+    }
+
+    if parsed.slot.is_some_and(|slot| slot > parsed.teams) {
+        return Err(format!("--slot can't be past the last of {} teams", parsed.teams));
+    }
+    // without knowing my pick, every team's timeout would draft for me
+    if parsed.auto_pick && parsed.slot.is_none() {
+        return Err("--auto-pick needs --slot".to_string());
+    }
+
+    Ok(parsed)
+}
+
+/// Splits leading `@TEAM` and `#tag` tokens off the search input, in any
+/// order, e.g. "@LAL #rookie james" gives `(Some("LAL"), Some("rookie"), "james")`.
+fn parse_search_filters(input: &str) -> (Option<&str>, Option<&str>, &str) {
+    let (mut team, mut tag) = (None, None);
+    let mut query = input;
+    while let Some(marker) = query.chars().next().filter(|c| *c == '@' || *c == '#') {
+        let (token, rest) = query[1..].split_once(' ').unwrap_or((&query[1..], ""));
+        if !token.is_empty() {
+            if marker == '@' {
+                team = Some(token);
+            } else {
+                tag = Some(token);
+            }
+        }
+        query = rest.trim_start();
+    }
+    (team, tag, query)
+}
+
+/// Splits the name into spans, with the characters at the matched indices in
+/// bold and underlined. The indices count characters of the normalized name,
+/// if normalizing changed the number of characters nothing is highlighted.
+fn highlight_matches(name: &str, matched: Option<&Vec<usize>>) -> Vec<Span<'static>> {
+    let matched = match matched {
+        Some(matched) if normalize(name).chars().count() == name.chars().count() => matched,
+        _ => return vec![Span::raw(name.to_string())],
+    };
+    let highlight = Style::default().add_modifier(Modifier::BOLD | Modifier::UNDERLINED);
+
+    let mut spans = Vec::new();
+    let mut run = String::new();
+    let mut run_matched = false;
+    for (i, c) in name.chars().enumerate() {
+        let is_matched = matched.contains(&i);
+        if is_matched != run_matched && !run.is_empty() {
+            let text = std::mem::take(&mut run);
+            spans.push(if run_matched { Span::styled(text, highlight) } else { Span::raw(text) });
+        }
+        run_matched = is_matched;
+        run.push(c);
+    }
+    if !run.is_empty() {
+        spans.push(if run_matched { Span::styled(run, highlight) } else { Span::raw(run) });
+    }
+    spans
+}
+
+/// Explains why the search has no results, suggesting which filter to loosen.
+fn empty_message(app: &App) -> String {
+    let (team, tag, query) = parse_search_filters(&app.input);
+    let position = app.selected_position != Position::ANY;
+    let pool = if app.show_drafted { "drafted" } else { "undrafted" };
+    let mut message = match (query.is_empty(), position) {
+        (false, true) => format!(
+            "No {} {:?} matches '{}' \u{2014} try clearing the position filter",
+            pool, app.selected_position, query
+        ),
+        (false, false) => format!("No {} player matches '{}' \u{2014} try a shorter search", pool, query),
+        (true, true) => format!(
+            "No {} {:?} players left \u{2014} try another position",
+            pool, app.selected_position
+        ),
+        (true, false) => format!("No {} players left", pool),
+    };
+    if let Some(team) = team {
+        message.push_str(&format!(", only {} players are searched", team.to_uppercase()));
+    }
+    if let Some(tag) = tag {
+        message.push_str(&format!(", only players tagged {} are searched", tag));
+    }
+    if let Some(thresholds) = app.threshold_summary() {
+        message.push_str(&format!(", only players with {}", thresholds));
+    }
+    message
+}
+
+/// Short form of a tag for the search results, e.g. "ROO" for "rookie".
+fn tag_badge(tag: &str) -> String {
+    tag.chars().take(3).collect::<String>().to_uppercase()
+}
+
+/// A pick in the recent picks ticker, e.g. "Nikola Jokic (mine)"
+fn recent_pick(name: &str, mine: bool) -> String {
+    format!("{} ({})", name, if mine { "mine" } else { "other" })
+}
+
+/// Pads the text with spaces up to the given display width, so columns line
+/// up even with wide characters. Longer text is left as is.
+fn pad_to_width(text: &str, width: usize) -> String {
+    let padding = width.saturating_sub(text.width());
+    format!("{}{}", text, " ".repeat(padding))
+}
+
+/// Cuts the text down to the given display width, ending it with "…" if
+/// anything was cut. Wide characters count double and combining marks stay
+/// with the character before them.
+fn truncate_to_width(text: &str, max: usize) -> String {
+    if text.width() <= max {
+        return text.to_string();
+    }
+    if max == 0 {
+        return String::new();
+    }
+    // leave room for the ellipsis
+    let budget = max - 1;
+    let mut truncated = String::new();
+    let mut width = 0;
+    for c in text.chars() {
+        let char_width = c.width().unwrap_or(0);
+        if width + char_width > budget {
+            break;
+        }
+        width += char_width;
+        truncated.push(c);
+    }
+    truncated.push('\u{2026}');
+    truncated
+}
+
+/// Quotes a CSV field if it contains a separator or a quote.
+fn csv_field(field: &str) -> String {
+    if field.contains(',') || field.contains('"') {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// Escapes the pipes that would otherwise end a Markdown table cell.
+fn markdown_cell(field: &str) -> String {
+    field.replace('|', "\\|")
+}
+
+/// Loads the player dataset, skipping players that fail to parse instead of
+/// rejecting the whole file. Returns the players that could be loaded and an
+/// error message for every one that couldn't.
+fn load_players(path: &str) -> (Vec<Player>, Vec<String>) {
+    let file = match File::open(path) {
+        Ok(file) => file,
+        Err(_) => return (Vec::new(), vec![format!("could not open data file at {}", path)]),
+    };
+
+    // use serde_json to deserialize the JSON data, one player at a time
+    let values: Vec<serde_json::Value> = match serde_json::from_reader(file) {
+        Ok(values) => values,
+        Err(err) => return (Vec::new(), vec![format!("could not parse data file at {}: {}", path, err)]),
+    };
+
+    parse_players(values)
+}
+
+/// Loads the player dataset from a CSV file with a header row naming the
+/// `name`, `team`, `position`, `pick_avg`, `round_avg` and `draft_percent`
+/// columns, and optionally `games`, `rank` and `tags`. Positions and tags are
+/// separated by `|` or `/`. Rows that fail to parse are skipped, with an
+/// error message for each.
+fn load_players_csv(path: &str) -> Result<(Vec<Player>, Vec<String>), Box<dyn Error>> {
+    let contents = std::fs::read_to_string(path)?;
+    let mut lines = contents.lines().filter(|line| !line.trim().is_empty());
+    let header = csv_fields(lines.next().ok_or("the CSV file is empty")?);
+    let column = |name: &str| {
+        header
+            .iter()
+            .position(|field| field.trim().eq_ignore_ascii_case(name))
+            .ok_or(format!("the CSV file has no {} column", name))
+    };
+    let (name, team, position, pick_avg, round_avg, draft_percent) = (
+        column("name")?,
+        column("team")?,
+        column("position")?,
+        column("pick_avg")?,
+        column("round_avg")?,
+        column("draft_percent")?,
+    );
+    let games = column("games").ok();
+    let rank = column("rank").ok();
+    let tags = column("tags").ok();
+
+    let mut players = Vec::new();
+    let mut errors = Vec::new();
+    for (i, line) in lines.enumerate() {
+        let fields = csv_fields(line);
+        let field = |index: usize| fields.get(index).map(|f| f.trim()).unwrap_or("");
+        let player = (|| -> Result<Player, String> {
+            Ok(Player {
+                name: field(name).to_string(),
+                team: field(team).to_string(),
+                position: Position::parse_list(field(position))?,
+                pick_avg: field(pick_avg).parse().map_err(|_| format!("bad pick_avg: {:?}", field(pick_avg)))?,
+                round_avg: field(round_avg).parse().map_err(|_| format!("bad round_avg: {:?}", field(round_avg)))?,
+                draft_percent: field(draft_percent).to_string(),
+                games: games.and_then(|games| field(games).parse().ok()),
+                rank: rank.and_then(|rank| field(rank).parse().ok()),
+                tags: tags.map_or(Vec::new(), |tags| {
+                    field(tags).split(['|', '/']).map(str::trim).filter(|t| !t.is_empty()).map(String::from).collect()
+                }),
+            })
+        })();
+        match player {
+            Ok(player) => players.push(player),
+            Err(err) => errors.push(format!("row #{} ({}): {}", i + 1, field(name), err)),
+        }
+    }
+
+    Ok((players, errors))
+}
+
+/// Splits a CSV line into its fields, the reverse of `csv_field`.
+fn csv_fields(line: &str) -> Vec<String> {
+    let mut fields = vec![String::new()];
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                fields.last_mut().unwrap().push('"');
+                chars.next();
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => fields.push(String::new()),
+            _ => fields.last_mut().unwrap().push(c),
+        }
+    }
+    fields
+}
+
+/// Fetches the player dataset over HTTP, parsing it as leniently as
+/// `load_players` does.
+fn fetch_players(url: &str) -> Result<(Vec<Player>, Vec<String>), Box<dyn Error>> {
+    let values: Vec<serde_json::Value> = ureq::get(url).call()?.into_json()?;
+    Ok(parse_players(values))
+}
+
+/// Deserializes every player on its own, so one bad entry doesn't reject the
+/// whole dataset.
+fn parse_players(values: Vec<serde_json::Value>) -> (Vec<Player>, Vec<String>) {
+    let mut players = Vec::new();
+    let mut errors = Vec::new();
+    for (i, value) in values.into_iter().enumerate() {
+        let name = value["name"].as_str().unwrap_or("unnamed").to_string();
+        match serde_json::from_value::<Player>(value) {
+            Ok(player) => players.push(player),
+            Err(err) => errors.push(format!("player #{} ({}): {}", i + 1, name, err)),
+        }
+    }
+
+    (players, errors)
+}
+
+/// Reads a draft file of any version, migrating older ones to the current
+/// format. Files written by a newer version of the app are rejected.
+fn read_picks(file: File) -> Result<Vec<PlayerPick>, Box<dyn Error>> {
+    match serde_json::from_reader(file)? {
+        AnySaveFile::Versioned(save_file) if save_file.version > SAVE_FILE_VERSION => Err(format!(
+            "draft file version {} is newer than the supported version {}",
+            save_file.version, SAVE_FILE_VERSION
+        )
+        .into()),
+        AnySaveFile::Versioned(save_file) => Ok(save_file.players),
+        AnySaveFile::Legacy(picks) => Ok(picks.into_iter().map(PlayerPick::from).collect()),
+    }
+}
+
+/// Reads a saved roster, or an empty one if the file doesn't exist. A file
+/// that can't be read is moved to `<file>.bak` so the next save doesn't
+/// overwrite it, and the roster starts empty with a warning to show.
+fn load_roster(path: &Path) -> (Vec<PlayerPick>, Option<String>) {
+    let file = match File::open(path) {
+        Ok(file) => file,
+        Err(_) => return (Vec::new(), None),
+    };
+    match read_picks(file) {
+        Ok(picks) => (picks, None),
+        Err(err) => {
+            let backup = PathBuf::from(format!("{}.bak", path.display()));
+            let warning = match std::fs::rename(path, &backup) {
+                Ok(()) => format!("{} is unreadable ({}), moved it to {}", path.display(), err, backup.display()),
+                Err(rename_err) => format!("{} is unreadable ({}) and can't be backed up: {}", path.display(), err, rename_err),
+            };
+            (Vec::new(), Some(warning))
+        }
+    }
+}
+
+/// Restores the terminal when dropped, so it is usable again after every
+/// exit path, errors and panics included.
+struct TerminalGuard;
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        let _ = disable_raw_mode();
+        let _ = execute!(io::stdout(), LeaveAlternateScreen, DisableMouseCapture, cursor::Show);
+    }
+}
+
+/// Writes the value to a temporary file first and renames it over the
+/// real one, so an interrupted save never leaves a truncated file behind.
+fn save_json_to<T: Serialize + ?Sized>(value: &T, path: &Path) -> Result<(), Box<dyn Error>> {
+    let tmp_path = PathBuf::from(format!("{}.tmp", path.display()));
+    let json = serde_json::to_string(value)?;
+    let mut file = File::create(&tmp_path)?;
+    file.write_all(json.as_bytes())?;
+    file.sync_all()?;
+    std::fs::rename(&tmp_path, path)?;
+    Ok(())
+}
+
+/// Prints every draft profile under `dir` with the number of picks saved in it.
+fn list_profiles(dir: &Path) -> Result<(), Box<dyn Error>> {
+    let drafts = dir.join("drafts");
+    if !drafts.is_dir() {
+        println!("No profiles in {}", drafts.display());
+        return Ok(());
+    }
+
+    let mut profiles: Vec<(String, PathBuf)> = std::fs::read_dir(&drafts)?
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.path().is_dir())
+        .map(|entry| (entry.file_name().to_string_lossy().to_string(), entry.path()))
+        .collect();
+    profiles.sort();
+
+    for (name, path) in profiles {
+        let picks = |filename: &str| {
+            File::open(path.join(filename))
+                .ok()
+                .and_then(|file| read_picks(file).ok())
+                .map_or(0, |picks| picks.len())
+        };
+        let (mine, other) = (picks("my_players.json"), picks("other_players.json"));
+        println!("{}: {} picks ({} mine, {} other)", name, mine + other, mine, other);
+    }
+    Ok(())
+}
+
+/// Asks a yes/no question on the terminal, anything but "y" or "yes" is a no.
+fn confirm(question: &str) -> io::Result<bool> {
+    print!("{}", question);
+    io::stdout().flush()?;
+    let mut answer = String::new();
+    io::stdin().read_line(&mut answer)?;
+    Ok(matches!(answer.trim().to_ascii_lowercase().as_str(), "y" | "yes"))
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let args: Vec<String> = env::args().collect();
+    let args = match parse_args(&args) {
+        Ok(args) => args,
+        Err(msg) => {
+            eprintln!("{}", msg);
+            eprintln!(
+                "usage: nba_tui [load|delete] [--data <path>] [--csv <path>] [--url <url>] [--dir <path>] [--teams <n>] \
+                 [--force] [--compare <path>] [--clock <seconds>] [--slot <n>] [--auto-pick] [--accessible] [--keepers <path>] [--profile <name>] [--list-profiles]"
+            );
+            std::process::exit(2);
+        }
+    };
+
+    if args.list_profiles {
+        return list_profiles(&args.dir);
+    }
+
+    // load players, from the URL if there is one, else from the CSV file if there is one
+    let local_players = |args: &Args| match &args.csv {
+        Some(path) => (
+            path.clone(),
+            load_players_csv(path).unwrap_or_else(|err| (Vec::new(), vec![format!("could not read {}: {}", path, err)])),
+        ),
+        None => (args.data_path.clone(), load_players(&args.data_path)),
+    };
+    let (source, (players, errors)) = match &args.url {
+        Some(url) => match fetch_players(url) {
+            Ok(loaded) => {
+                eprintln!("using players from {}", url);
+                (url.clone(), loaded)
+            }
+            Err(err) => {
+                let (source, loaded) = local_players(&args);
+                eprintln!("could not fetch {}: {}", url, err);
+                eprintln!("using players from {}", source);
+                (source, loaded)
+            }
+        },
+        None => local_players(&args),
+    };
+    if players.is_empty() && !errors.is_empty() {
+        for error in errors.iter() {
+            eprintln!("{}", error);
+        }
+        if args.csv.is_none() && !std::path::Path::new(&args.data_path).exists() {
+            eprintln!(
+                "expected the player dataset at {}, pass --data <path> to use another file",
+                args.data_path
+            );
+        }
+        std::process::exit(1);
+    }
+    if !errors.is_empty() {
+        eprintln!("loaded {} players, skipped {} (see below)", players.len(), errors.len());
+        for error in errors.iter() {
+            eprintln!("  {}", error);
+        }
+    }
+
+    // without a profile the draft files stay where they always were
+    let draft_dir = match &args.profile {
+        Some(profile) => args.dir.join("drafts").join(profile),
+        None => args.dir.clone(),
+    };
+    std::fs::create_dir_all(&draft_dir)?;
+    let my_players_path = draft_dir.join("my_players.json");
+    let other_players_path = draft_dir.join("other_players.json");
+    let session_path = draft_dir.join("session.json");
+    let slots_path = args.dir.join("slots.json");
+    let notes_path = args.dir.join("notes.json");
+    let watchlist_path = args.dir.join("watchlist.json");
+    let excluded_path = args.dir.join("excluded.json");
+    let keys_path = args.dir.join("keys.toml");
+
+    // bad data, but still draftable
+    let positionless = players.iter().filter(|p| p.position.is_empty()).count();
+    if positionless > 0 {
+        eprintln!("{} players have no listed position and only fit ANY, UTIL and BENCH", positionless);
+    }
+
+    // create app and run it
+    let has_ranks = players.iter().any(|p| p.rank.is_some());
+    let mut app = App {
+        has_ranks,
+        sort_mode: SortMode::default_for(has_ranks),
+        normalized_names: players.iter().map(|p| normalize(&p.name)).collect(),
+        all_players: players,
+        csv_source: args.csv.as_ref() == Some(&source),
+        data_source: source.clone(),
+        dir: args.dir.clone(),
+        draft_dir,
+        draft: DraftState::new(args.teams),
+        status_message: match (errors.len(), positionless) {
+            (0, 0) => None,
+            (0, positionless) => Some(format!("{} players have no position, they only fit ANY, UTIL and BENCH", positionless)),
+            (skipped, 0) => Some(format!("Skipped {} malformed players in {}", skipped, source)),
+            (skipped, positionless) => Some(format!(
+                "Skipped {} malformed players in {}, {} have no position",
+                skipped, source, positionless
+            )),
+        },
+        ..App::default()
+    };
+
+    // the league's roster shape, if it differs from the default one
+    let slots_file = File::open(&slots_path);
+    if let Ok(file) = slots_file {
+        match serde_json::from_reader::<_, Vec<SlotConfig>>(file) {
+            Ok(slots) => app.slots = slots.into_iter().map(|s| (s.position, s.count)).collect(),
+            Err(err) => eprintln!("ignoring {}: {}", slots_path.display(), err),
+        }
+    }
+
+    // remapped keys, all or nothing so a bad file can't leave an action unreachable
+    if let Ok(contents) = std::fs::read_to_string(&keys_path) {
+        match toml::from_str::<KeyBindings>(&contents) {
+            Ok(keys) => {
+                let conflicts = keys.conflicts();
+                if conflicts.is_empty() {
+                    app.keys = keys;
+                } else {
+                    eprintln!("ignoring {}, using the default keys:", keys_path.display());
+                    for conflict in conflicts.iter() {
+                        eprintln!("  {}", conflict);
+                    }
+                }
+            }
+            Err(err) => eprintln!("ignoring {}: {}", keys_path.display(), err),
+        }
+    }
+
+    // notes are loaded regardless of the command, they aren't part of the draft
+    let notes_file = File::open(&notes_path);
+    if let Ok(file) = notes_file {
+        match serde_json::from_reader::<_, HashMap<String, String>>(file) {
+            Ok(notes) => app.notes = notes,
+            Err(err) => eprintln!("ignoring {}: {}", notes_path.display(), err),
+        }
+    }
+    let watchlist_file = File::open(&watchlist_path);
+    if let Ok(file) = watchlist_file {
+        match serde_json::from_reader::<_, Vec<String>>(file) {
+            Ok(watchlist) => app.watchlist = watchlist,
+            Err(err) => eprintln!("ignoring {}: {}", watchlist_path.display(), err),
+        }
+    }
+
+    let excluded_file = File::open(&excluded_path);
+    if let Ok(file) = excluded_file {
+        match serde_json::from_reader::<_, Vec<String>>(file) {
+            Ok(excluded) => app.excluded = excluded,
+            Err(err) => eprintln!("ignoring {}: {}", excluded_path.display(), err),
+        }
+    }
+
+    // keepers aren't part of the saved draft, so `delete` doesn't touch them
+    let (keepers_mine, keepers_other) = match &args.keepers {
+        Some(path) => {
+            let file = File::open(path).map_err(|err| format!("can't open {}: {}", path.display(), err))?;
+            match serde_json::from_reader::<_, KeeperFile>(file)
+                .map_err(|err| format!("can't read {}: {}", path.display(), err))?
+            {
+                KeeperFile::Teams { mine, other } => (mine, other),
+                KeeperFile::Mine(mine) => (mine, Vec::new()),
+            }
+        }
+        None => (Vec::new(), Vec::new()),
+    };
+    app.keepers = keepers_mine.iter().chain(keepers_other.iter()).cloned().collect();
+
+    match args.command.as_deref() {
+        Some("load") => {
+            app.resumed = true;
+            // a corrupt file costs that roster, not the whole session
+            let (my_players, my_warning) = load_roster(&my_players_path);
+            app.my_players = app.restore_picks(my_players);
+            let (other_players, other_warning) = load_roster(&other_players_path);
+            app.other_players = app.restore_picks(other_players);
+
+            let warnings: Vec<String> = app
+                .status_message
+                .take()
+                .into_iter()
+                .chain(my_warning)
+                .chain(other_warning)
+                .collect();
+            if !warnings.is_empty() {
+                app.status_message = Some(warnings.join(" | "));
+            }
+
+            for name in app.validate_against_dataset() {
+                eprintln!("{} is not in the dataset and will be ignored", name);
+            }
+
+            let conflicts = app.reconcile_rosters();
+            for name in conflicts.iter() {
+                eprintln!("{} was drafted by both teams, keeping them on mine", name);
+            }
+            if !conflicts.is_empty() {
+                app.save_players(&app.other_players, "other_players.json")?;
+            }
+
+            // an unreadable session is not worth failing over, keep the defaults
+            let mut saved_picks = Vec::new();
+            let session_file = File::open(&session_path);
+            if let Ok(file) = session_file {
+                if let Ok(session) = serde_json::from_reader::<_, Session>(file) {
+                    app.selected_position = session.selected_position;
+                    if let Some(sort_mode) = session.sort_mode {
+                        // the dataset may have lost its ranks since
+                        if sort_mode != SortMode::Rank || app.has_ranks {
+                            app.sort_mode = sort_mode;
+                        }
+                    }
+                    app.display_stat = session.display_stat;
+                    app.min_draft_percent = session.min_draft_percent;
+                    app.max_pick_avg = session.max_pick_avg;
+                    saved_picks = session.picks;
+                }
+            }
+            // carries on from the right overall pick, with the board filled in
+            app.restore_draft(saved_picks);
+        }
+        Some("delete") => {
+            if !args.force && !confirm("Delete saved draft? [y/N] ")? {
+                println!("Keeping the saved draft.");
+                return Ok(());
+            }
+
+            let my_players_file = File::open(&my_players_path);
+            if my_players_file.is_ok() {
+                std::fs::remove_file(&my_players_path)?;
+            }
+            let my_players_file = File::open(&other_players_path);
+            if my_players_file.is_ok() {
+                std::fs::remove_file(&other_players_path)?;
+            }
+        }
+        _ => {}
+    }
+
+    app.add_keepers(&keepers_mine, &keepers_other);
+    for name in app.keepers.iter().filter(|p| app.get_player(p).is_none()) {
+        eprintln!("keeper {} is not in the dataset and will be ignored", name);
+    }
+
+    // someone else's draft, for reviewing it next to mine
+    if let Some(path) = &args.compare {
+        let file = File::open(path).map_err(|err| format!("can't open {}: {}", path.display(), err))?;
+        app.compare_players = read_picks(file)?.into_iter().map(|pick| pick.name).collect();
+        for name in app.compare_players.iter().filter(|p| app.get_player(p).is_none()) {
+            eprintln!("{} is not in the dataset and will be ignored", name);
+        }
+    }
+
+    // counts the remaining players before anything is searched
+    app.filter_players();
+
+    app.pick_clock = args.clock.map(Duration::from_secs);
+    app.auto_pick = args.auto_pick;
+    app.my_slot = args.slot.map(|slot| slot - 1);
+    app.accessible = args.accessible;
+    app.start_clock();
+
+    // setup terminal, the guard restores it however we leave from here on
+    enable_raw_mode()?;
+    let guard = TerminalGuard;
+    let mut stdout = io::stdout();
+    execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
+    let backend = CrosstermBackend::new(stdout);
+    let mut terminal = Terminal::new(backend)?;
+
+    let res = run_app(&mut terminal, app);
+
+    // restore terminal before printing anything
+    drop(guard);
+
+    if let Err(err) = res {
+        println!("{:?}", err)
+    }
+
+    Ok(())
+}
+
+fn run_app<B: Backend>(terminal: &mut Terminal<B>, mut app: App) -> io::Result<()> {
+    loop {
+        terminal.draw(|f| ui(f, &mut app))?;
+
+        // a running clock needs redrawing even when no key is pressed
+        if app.pick_deadline.is_some() && !event::poll(CLOCK_TICK)? {
+            match app.expire_clock() {
+                Ok(Some(name)) => app.status_message = Some(format!("Time's up, drafted {}", name)),
+                Ok(None) => {}
+                Err(err) => app.status_message = Some(format!("Auto pick failed: {}", err)),
+            }
+            continue;
+        }
+
+        let event = event::read()?;
+        // the next draw lays everything out again for the new size
+        if let Event::Resize(_, _) = event {
+            continue;
+        }
+        if let Event::Mouse(mouse) = event {
+            app.handle_mouse(mouse);
+        }
+        if let Event::Key(key) = event {
+            // the overlay swallows every key so nothing is drafted by accident
+            if app.show_help {
+                if matches!(key.code, KeyCode::Char('?') | KeyCode::Esc) {
+                    app.show_help = false;
+                }
+                continue;
+            }
+            if app.detail_player.is_some() {
+                if key.code == KeyCode::Esc {
+                    app.detail_player = None;
+                }
+                continue;
+            }
+            // any other key than y cancels the export
+            if let Some(export) = app.pending_export.take() {
+                app.status_message = Some(if let KeyCode::Char('y') | KeyCode::Char('Y') = key.code {
+                    app.export(export)
+                } else {
+                    "Export cancelled".to_string()
+                });
+                continue;
+            }
+            // any other key than y keeps the player drafted
+            if let Some(name) = app.pending_removal.take() {
+                app.status_message = None;
+                if let KeyCode::Char('y') | KeyCode::Char('Y') = key.code {
+                    if let Err(err) = app.remove_drafted(&name) {
+                        app.status_message = Some(format!("Removing the player failed: {}", err));
+                    }
+                } else {
+                    app.status_message = Some(format!("Kept {} drafted", name));
+                }
+                continue;
+            }
+            // a question mark is part of the text while writing a note
+            if key.code == KeyCode::Char('?') && app.input_mode != InputMode::Noting {
+                app.show_help = true;
+                continue;
+            }
+            app.status_message = None;
+            // while typing a note or a threshold the arrows belong to the text
+            let typing = matches!(app.input_mode, InputMode::Noting | InputMode::Threshold);
+            if key.code == KeyCode::Right && !typing {
+                app.set_position(app.selected_position.next());
+            } else if key.code == KeyCode::Left && !typing {
+                app.set_position(app.selected_position.prev());
+            }
+            match app.input_mode {
+                InputMode::Idle => match key.code {
+                    KeyCode::Char(c) if KeyBindings::matches(c, app.keys.search) => {
+                        app.input_mode = InputMode::Searching;
+                        app.filter_players();
+                    }
+                    KeyCode::Enter | KeyCode::Up | KeyCode::Down => {
+                        app.input_mode = InputMode::Searching;
+                        app.filter_players();
+                    }
+                    KeyCode::Char(c) if KeyBindings::matches(c, app.keys.quit) => {
+                        // the next `load` picks up where this session left off, but a
+                        // run that neither loaded nor drafted keeps the saved one
+                        if app.resumed || !app.draft.picks.is_empty() {
+                            app.save_session().map_err(|err| io::Error::other(err.to_string()))?;
+                        }
                         return Ok(());
                     }
-                    KeyCode::Char('l') => {
+                    KeyCode::Char(c) if KeyBindings::matches(c, app.keys.list) => {
                         app.input_mode = InputMode::Listing;
                     }
+                    KeyCode::Char('f') => {
+                        app.start_position_jump();
+                    }
+                    KeyCode::Char('b') => {
+                        app.input_mode = InputMode::Recommending;
+                        app.selected_player = None;
+                        app.filter_players();
+                    }
+                    KeyCode::Char('d') => {
+                        app.input_mode = InputMode::Board;
+                    }
+                    KeyCode::Char('0') => {
+                        app.set_position(Position::ANY);
+                    }
+                    KeyCode::Char('c') => {
+                        app.show_position_picker = !app.show_position_picker;
+                    }
+                    KeyCode::Char('r') => {
+                        app.clear_filters();
+                    }
+                    KeyCode::Char('R') => {
+                        let source = app.data_source.clone();
+                        if let Err(err) = app.reload_dataset(&source) {
+                            app.status_message = Some(format!("Reloading {} failed: {}", source, err));
+                        }
+                    }
+                    KeyCode::Char('m') => {
+                        app.start_threshold(Threshold::MinDraftPercent);
+                    }
+                    KeyCode::Backspace => {
+                        app.swap_to_previous_position();
+                    }
+                    KeyCode::Char('p') => {
+                        app.start_threshold(Threshold::MaxPickAvg);
+                    }
                     _ => {}
                 },
-                InputMode::Searching => match key.code {
+                InputMode::Board => {
+                    if let KeyCode::Esc | KeyCode::Char('d') | KeyCode::Char('q') = key.code {
+                        app.input_mode = InputMode::Idle;
+                    }
+                }
+                InputMode::Threshold => match key.code {
+                    KeyCode::Enter => {
+                        if let Err(err) = app.finish_threshold() {
+                            app.status_message = Some(err);
+                        }
+                        app.input_mode = InputMode::Idle;
+                    }
+                    KeyCode::Char(c) if c.is_ascii_digit() || c == '.' => {
+                        app.threshold_input.push(c);
+                    }
+                    KeyCode::Backspace => {
+                        app.threshold_input.pop();
+                    }
+                    KeyCode::Esc => {
+                        app.threshold_input.clear();
+                        app.input_mode = InputMode::Idle;
+                    }
+                    _ => {}
+                },
+                InputMode::Recommending => match key.code {
+                    KeyCode::Up | KeyCode::Char('k') => {
+                        app.select_previous();
+                    }
+                    KeyCode::Down | KeyCode::Char('j') => {
+                        app.select_next();
+                    }
+                    KeyCode::Char('g') => {
+                        app.select_first();
+                    }
+                    KeyCode::Char('G') => {
+                        app.select_last();
+                    }
+                    KeyCode::Backspace => {
+                        app.swap_to_previous_position();
+                    }
                     KeyCode::Enter => {
                         if let Some(selected) = app.selected_player {
-                            app.candidate_player = app.filtered_players[selected].clone();
-                            app.input_mode = InputMode::Picking;
-                        } else {
-                            if app.filtered_players.len() > 0 {
-                                app.selected_player = Some(0);
-                                app.input = app.filtered_players[0].clone();
-                                app.filter_players();
-                            }
+                            app.start_picking(app.filtered_players[selected].clone());
+                        } else if !app.filtered_players.is_empty() {
+                            app.selected_player = Some(0);
+                        }
+                    }
+                    KeyCode::Char(c) if key.modifiers.contains(KeyModifiers::ALT) && c.is_alphabetic() => {
+                        app.jump_to_letter(c);
+                    }
+                    KeyCode::Esc | KeyCode::Char('b') | KeyCode::Char('q') => {
+                        app.input_mode = InputMode::Idle;
+                        app.selected_player = None;
+                        app.filter_players();
+                    }
+                    _ => {}
+                },
+                InputMode::Searching => match key.code {
+                    KeyCode::Enter if app.show_drafted => {
+                        if let Some(selected) = app.selected_player {
+                            app.start_removing(app.filtered_players[selected].clone());
                         }
                     }
-                    KeyCode::Tab => {
-                        if app.filtered_players.len() > 0 {
+                    KeyCode::Enter => {
+                        if let Some(selected) = app.selected_player {
+                            app.start_picking(app.filtered_players[selected].clone());
+                        } else if !app.filtered_players.is_empty() {
                             app.selected_player = Some(0);
                             app.input = app.filtered_players[0].clone();
                             app.filter_players();
                         }
                     }
+                    KeyCode::Tab if !app.completion.is_empty() => {
+                        let completion = std::mem::take(&mut app.completion);
+                        app.input.push_str(&completion);
+                        app.filter_players();
+                    }
+                    KeyCode::Tab if !app.filtered_players.is_empty() => {
+                        app.selected_player = Some(0);
+                        app.input = app.filtered_players[0].clone();
+                        app.filter_players();
+                    }
+                    // with nothing selected, Up walks back through past queries
+                    KeyCode::Up
+                        if app.selected_player.is_none()
+                            && (app.input.is_empty() || app.history_index.is_some()) =>
+                    {
+                        app.recall_previous_query();
+                    }
                     KeyCode::Up => {
+                        app.select_previous();
+                    }
+                    KeyCode::Down => {
+                        app.history_index = None;
+                        app.select_next();
+                    }
+                    // digits pick rows from 1, so 0 is free to show every position
+                    KeyCode::Char('0') => {
+                        app.set_position(Position::ANY);
+                    }
+                    // letters are always typed, names start with j, k and g too
+                    KeyCode::Home => {
+                        app.select_first();
+                    }
+                    KeyCode::End => {
+                        app.select_last();
+                    }
+                    // names can contain a hyphen, so these only work before typing
+                    KeyCode::Char('+') if app.input.is_empty() => {
+                        app.change_result_limit(1);
+                        app.status_message = Some(format!("Showing {} players", app.result_limit));
+                    }
+                    KeyCode::Char('-') if app.input.is_empty() => {
+                        app.change_result_limit(-1);
+                        app.status_message = Some(format!("Showing {} players", app.result_limit));
+                    }
+                    KeyCode::Char(c) if key.modifiers.contains(KeyModifiers::ALT) && c.is_alphabetic() => {
+                        app.jump_to_letter(c);
+                    }
+                    KeyCode::Char('y') if key.modifiers.contains(KeyModifiers::CONTROL) => {
                         if let Some(selected) = app.selected_player {
-                            if selected > 0 {
-                                app.selected_player = Some(selected - 1);
+                            let name = app.filtered_players[selected].clone();
+                            app.status_message = Some(app.copy_name(&name));
+                        }
+                    }
+                    KeyCode::Char('n') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                        let position = app.most_needed_position();
+                        app.status_message = Some(if app.slots_all_filled() {
+                            "Roster full".to_string()
+                        } else {
+                            format!("Most needed: {:?}", position)
+                        });
+                        app.set_position(position);
+                    }
+                    KeyCode::Char('l') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                        app.clear_filters();
+                    }
+                    KeyCode::Char('u') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                        app.input.clear();
+                        app.typed_query.clear();
+                        app.history_index = None;
+                        app.selected_player = None;
+                        app.filter_players();
+                    }
+                    KeyCode::Char('s') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                        app.sort_mode = app.sort_mode.next();
+                        // sorting by rank is sorting by pick avg without ranks
+                        if app.sort_mode == SortMode::Rank && !app.has_ranks {
+                            app.sort_mode = app.sort_mode.next();
+                        }
+                        app.filter_players();
+                    }
+                    KeyCode::Char('d') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                        app.display_stat = app.display_stat.next();
+                    }
+                    KeyCode::Char('z') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                        if let Err(err) = app.undo() {
+                            app.status_message = Some(format!("Undo failed: {}", err));
+                        }
+                        app.selected_player = None;
+                    }
+                    KeyCode::Char('f') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                        app.start_position_jump();
+                    }
+                    KeyCode::Char('w') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                        if let Some(selected) = app.selected_player {
+                            let name = app.filtered_players[selected].clone();
+                            if let Err(err) = app.toggle_watchlist(name) {
+                                app.status_message = Some(format!("Saving the watchlist failed: {}", err));
                             }
                         }
                     }
-                    KeyCode::Down => {
+                    KeyCode::Char('a') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                        app.status_message = Some(match app.draft_top_available() {
+                            Ok(Some(name)) => format!("Drafted {}", name),
+                            Ok(None) if app.at_capacity() => "Roster full — remove a player first".to_string(),
+                            Ok(None) => "Nobody left to draft".to_string(),
+                            Err(err) => format!("Drafting failed: {}", err),
+                        });
+                    }
+                    KeyCode::Char('x') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                        if let Some(selected) = app.selected_player {
+                            let name = app.filtered_players[selected].clone();
+                            app.status_message = Some(match app.toggle_excluded(name.clone()) {
+                                Ok(()) => format!("Excluded {}, L then H lists excluded players", name),
+                                Err(err) => format!("Saving the excluded players failed: {}", err),
+                            });
+                        }
+                    }
+                    KeyCode::Char('o') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                        if let Some(selected) = app.selected_player {
+                            app.detail_player = Some(app.filtered_players[selected].clone());
+                        }
+                    }
+                    KeyCode::Char('t') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                        app.show_teammates = !app.show_teammates;
+                    }
+                    KeyCode::Char('r') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                        app.show_drafted = !app.show_drafted;
+                        app.filter_players();
+                    }
+                    KeyCode::Char('v') if key.modifiers.contains(KeyModifiers::CONTROL) => {
                         if let Some(selected) = app.selected_player {
-                            if selected < app.filtered_players.len() - 1 {
-                                app.selected_player = Some(selected + 1);
+                            let name = app.filtered_players[selected].clone();
+                            if !app.comparison.contains(&name) {
+                                app.comparison.push(name.clone());
                             }
-                        } else {
-                            if !app.filtered_players.is_empty() {
-                                app.selected_player = Some(0);
+                            if app.comparison.len() == 2 {
+                                app.input_mode = InputMode::Comparing;
+                            } else {
+                                app.status_message = Some(format!("Comparing {}, pick another player with Ctrl+V", name));
                             }
                         }
                     }
                     KeyCode::Char(c) => {
                         if c.is_ascii_digit() {
+                            // digits pick among the rows currently on screen
                             let c = c.to_digit(10).unwrap() as usize;
-                            if c <= app.filtered_players.len() {
+                            let index = app.scroll_offset + c;
+                            if c > 0 && c <= app.result_limit && index <= app.filtered_players.len() {
                                 app.selected_player = Some(0);
-                                app.input = app.filtered_players[c - 1].clone();
+                                app.input = app.filtered_players[index - 1].clone();
                                 app.filter_players();
                             }
                         } else {
                             app.input.push(c);
+                            app.typed_query = app.input.clone();
+                            app.history_index = None;
                             app.filter_players();
                         }
                     }
+                    KeyCode::Backspace if app.input.is_empty() => {
+                        app.swap_to_previous_position();
+                    }
                     KeyCode::Backspace => {
                         app.input.pop();
+                        app.typed_query = app.input.clone();
+                        app.history_index = None;
                         app.filter_players();
                     }
                     KeyCode::Esc => {
                         app.candidate_player.clear();
+                        app.typed_query.clear();
+                        app.history_index = None;
                         app.input.clear();
                         app.filter_players();
                         app.selected_player = None;
@@ -321,36 +3274,153 @@ fn run_app<B: Backend>(terminal: &mut Terminal<B>, mut app: App) -> io::Result<(
                     _ => {}
                 },
                 InputMode::Picking => match key.code {
-                    KeyCode::Char('a') | KeyCode::Char('A') | KeyCode::Enter => {
-                        app.my_players.push(app.candidate_player.clone());
-                        app.save_players(&app.my_players, "my_players.json").unwrap();
-                        app.candidate_player.clear();
-                        app.input.clear();
-                        app.filter_players();
-                        app.selected_player = None;
-                        app.input_mode = InputMode::Searching;
+                    KeyCode::Char(c) if KeyBindings::matches(c, app.keys.add_to_my_team) => {
+                        if let Err(err) = app.pick_for_my_team() {
+                            app.status_message = Some(format!("Drafting failed: {}", err));
+                        }
                     }
-                    KeyCode::Char('b') | KeyCode::Char('B') => {
-                        app.other_players.push(app.candidate_player.clone());
-                        app.save_players(&app.other_players, "other_players.json").unwrap();
-                        app.candidate_player.clear();
-                        app.input.clear();
-                        app.filter_players();
-                        app.selected_player = None;
-                        app.input_mode = InputMode::Searching;
+                    KeyCode::Enter => {
+                        if let Err(err) = app.pick_for_my_team() {
+                            app.status_message = Some(format!("Drafting failed: {}", err));
+                        }
+                    }
+                    KeyCode::Char('o') | KeyCode::Char('O') => {
+                        if let Err(err) = app.finish_pick(true) {
+                            app.status_message = Some(format!("Drafting failed: {}", err));
+                        }
+                    }
+                    KeyCode::Char(c) if KeyBindings::matches(c, app.keys.add_to_other_team) => {
+                        if let Err(err) = app.finish_pick(false) {
+                            app.status_message = Some(format!("Drafting failed: {}", err));
+                        }
+                    }
+                    KeyCode::Char('n') | KeyCode::Char('N') => {
+                        app.start_noting();
                     }
                     KeyCode::Esc => {
                         app.candidate_player.clear();
                         app.input.clear();
-                        app.filter_players();
+                        app.input_mode = app.pick_return_mode;
                         app.selected_player = None;
+                        app.filter_players();
+                    }
+                    _ => {}
+                },
+                InputMode::Noting => match key.code {
+                    KeyCode::Enter => {
+                        if let Err(err) = app.finish_noting() {
+                            app.status_message = Some(format!("Saving notes failed: {}", err));
+                        }
+                    }
+                    KeyCode::Char(c) => {
+                        app.note_input.push(c);
+                    }
+                    KeyCode::Backspace => {
+                        app.note_input.pop();
+                    }
+                    KeyCode::Esc => {
+                        app.note_input.clear();
+                        app.input_mode = InputMode::Picking;
+                    }
+                    _ => {}
+                },
+                InputMode::PositionJump => match key.code {
+                    KeyCode::Char(c) => {
+                        app.jump_keys.push(c.to_ascii_lowercase());
+                        if let Some(position) = Position::from_shortcut(&app.jump_keys) {
+                            app.set_position(position);
+                            app.input_mode = app.jump_return_mode;
+                        } else if !Position::is_shortcut_prefix(&app.jump_keys) {
+                            app.input_mode = app.jump_return_mode;
+                        }
+                    }
+                    // anything else cancels the jump without touching the filter
+                    _ => {
+                        app.input_mode = app.jump_return_mode;
+                    }
+                },
+                InputMode::Comparing => {
+                    if let KeyCode::Esc = key.code {
+                        app.comparison.clear();
                         app.input_mode = InputMode::Searching;
                     }
-                    _ => {}
-                },
-                InputMode::Listing => match key.code {
-                    KeyCode::Char('q') => {
-                        app.input_mode = InputMode::Idle;
+                }
+                InputMode::Listing => match key.code {
+                    KeyCode::Char('q') => {
+                        app.input_mode = InputMode::Idle;
+                    }
+                    KeyCode::Up | KeyCode::Down
+                        if key.modifiers.contains(KeyModifiers::SHIFT) && !app.show_excluded && !app.show_watchlist =>
+                    {
+                        if let Some((_, Some(index))) = app.roster_rows().get(app.selected_slot).cloned() {
+                            if let Err(err) = app.reorder_listed_player(index, key.code == KeyCode::Up) {
+                                app.status_message = Some(format!("Saving the new order failed: {}", err));
+                            }
+                        }
+                    }
+                    KeyCode::Up if app.selected_slot > 0 => {
+                        app.selected_slot -= 1;
+                    }
+                    KeyCode::Down if app.selected_slot + 1 < app.listing_len() => {
+                        app.selected_slot += 1;
+                    }
+                    KeyCode::Char('h') | KeyCode::Char('H') => {
+                        app.show_excluded = !app.show_excluded;
+                        app.show_watchlist = false;
+                        app.selected_slot = 0;
+                    }
+                    KeyCode::Char('d') | KeyCode::Char('D') if app.show_excluded => {
+                        if let Some(name) = app.excluded.get(app.selected_slot).cloned() {
+                            if let Err(err) = app.toggle_excluded(name) {
+                                app.status_message = Some(format!("Saving the excluded players failed: {}", err));
+                            }
+                        }
+                        app.selected_slot = app.selected_slot.min(app.listing_len().saturating_sub(1));
+                    }
+                    KeyCode::Char('d') | KeyCode::Char('D') if app.show_watchlist => {
+                        if let Some(name) = app.watchlist.get(app.selected_slot).cloned() {
+                            if let Err(err) = app.toggle_watchlist(name) {
+                                app.status_message = Some(format!("Saving the watchlist failed: {}", err));
+                            }
+                        }
+                        app.selected_slot = app.selected_slot.min(app.listing_len().saturating_sub(1));
+                    }
+                    KeyCode::Tab if !app.show_watchlist && !app.show_excluded => {
+                        app.list_other_team = !app.list_other_team;
+                        app.selected_slot = 0;
+                    }
+                    KeyCode::Char('d') | KeyCode::Char('D') => {
+                        if let Some((_, Some(index))) = app.roster_rows().get(app.selected_slot).cloned() {
+                            if let Err(err) = app.remove_listed_player(index) {
+                                app.status_message = Some(format!("Removing the player failed: {}", err));
+                            }
+                        }
+                        app.selected_slot = app.selected_slot.min(app.listing_len().saturating_sub(1));
+                    }
+                    KeyCode::Char('m') | KeyCode::Char('M') if !app.show_watchlist && !app.show_excluded => {
+                        if let Some((name, Some(index))) = app.roster_rows().get(app.selected_slot).cloned() {
+                            let from_mine = !app.list_other_team;
+                            app.status_message = Some(match app.move_player(index, from_mine) {
+                                Ok(()) if from_mine => format!("Moved {} to the other team", name),
+                                Ok(()) => format!("Moved {} to my team", name),
+                                Err(err) => format!("Moving the player failed: {}", err),
+                            });
+                        }
+                        app.selected_slot = app.selected_slot.min(app.listing_len().saturating_sub(1));
+                    }
+                    KeyCode::Char('o') | KeyCode::Char('O') => {
+                        app.side_by_side = !app.side_by_side;
+                    }
+                    KeyCode::Char('w') | KeyCode::Char('W') => {
+                        app.show_watchlist = !app.show_watchlist;
+                        app.show_excluded = false;
+                        app.selected_slot = 0;
+                    }
+                    KeyCode::Char('e') | KeyCode::Char('E') => {
+                        app.start_export(Export::Csv);
+                    }
+                    KeyCode::Char('x') | KeyCode::Char('X') => {
+                        app.start_export(Export::Markdown);
                     }
                     _ => {}
                 },
@@ -359,7 +3429,8 @@ fn run_app<B: Backend>(terminal: &mut Terminal<B>, mut app: App) -> io::Result<(
     }
 }
 
-fn ui<B: Backend>(f: &mut Frame<B>, app: &App) {
+fn ui<B: Backend>(f: &mut Frame<B>, app: &mut App) {
+    let position_height = if app.show_position_picker { 3 } else { 1 };
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .margin(3)
@@ -368,24 +3439,50 @@ fn ui<B: Backend>(f: &mut Frame<B>, app: &App) {
                 Constraint::Length(1),
                 Constraint::Length(3),
                 Constraint::Min(1),
-                Constraint::Length(3),
+                Constraint::Length(position_height),
+                Constraint::Length(1),
+                Constraint::Length(1),
             ]
             .as_ref(),
         )
         .split(f.size());
 
-    let (msg, style) = match app.input_mode {
+    // the list block borders take up two rows
+    let available = chunks[2].height.saturating_sub(2) as usize;
+    app.result_limit = app.manual_result_limit.map_or(available, |limit| limit.min(available));
+    app.list_area = chunks[2];
+    app.clamp_selection();
+
+    let (mut msg, style) = match app.input_mode {
         InputMode::Idle => (
             vec![
                 Span::raw("Press "),
-                Span::styled("q", Style::default().add_modifier(Modifier::BOLD)),
+                Span::styled(app.keys.quit.to_string(), Style::default().add_modifier(Modifier::BOLD)),
                 Span::raw(" to exit, "),
-                Span::styled("s or Enter", Style::default().add_modifier(Modifier::BOLD)),
+                Span::styled(format!("{} or Enter", app.keys.search), Style::default().add_modifier(Modifier::BOLD)),
                 Span::raw(" to start searching,"),
-                Span::styled("l", Style::default().add_modifier(Modifier::BOLD)),
-                Span::raw(" to start listing."),
+                Span::styled(app.keys.list.to_string(), Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(" to start listing, "),
+                Span::styled("f", Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(" to jump to a position, "),
+                Span::styled("b", Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(" for the best available players, "),
+                Span::styled("d", Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(" for the draft board, "),
+                Span::styled("c", Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(" to collapse the positions, "),
+                Span::styled("r", Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(" to clear every filter, "),
+                Span::styled("R", Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(" to reload the players, "),
+                Span::styled("m/p", Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(" to set a minimum draft % or maximum pick avg."),
             ],
-            Style::default().add_modifier(Modifier::RAPID_BLINK),
+            if app.accessible {
+                Style::default()
+            } else {
+                Style::default().add_modifier(Modifier::RAPID_BLINK)
+            },
         ),
         InputMode::Searching => (
             vec![
@@ -393,21 +3490,99 @@ fn ui<B: Backend>(f: &mut Frame<B>, app: &App) {
                 Span::styled("Esc", Style::default().add_modifier(Modifier::BOLD)),
                 Span::raw(" to stop searching, "),
                 Span::styled("Up/Down Arrows", Style::default().add_modifier(Modifier::BOLD)),
-                Span::raw(" to select player,"),
+                Span::raw(" to select player ("),
+                Span::styled("Home/End", Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(" for the first/last), "),
                 Span::styled("Enter", Style::default().add_modifier(Modifier::BOLD)),
-                Span::raw(" to pick the player"),
+                Span::raw(if app.show_drafted { " to remove the player from the draft, " } else { " to pick the player, " }),
+                Span::styled("Ctrl+R", Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(if app.show_drafted { " to search undrafted players, " } else { " to search drafted players, " }),
+                Span::styled("Ctrl+U", Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(" to clear the search ("),
+                Span::styled("Ctrl+L", Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(" every filter), "),
+                Span::styled("Ctrl+S", Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(" to change sorting, "),
+                Span::styled("Ctrl+Z", Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(" to undo the last pick, "),
+                Span::styled("Ctrl+F", Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(" to jump to a position, "),
+                Span::styled("Ctrl+W", Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(" to watch the player"),
             ],
             Style::default(),
         ),
         InputMode::Picking => (
             vec![
                 Span::raw("Press "),
-                Span::styled("A or Enter", Style::default().add_modifier(Modifier::BOLD)),
+                Span::styled(
+                    format!("{} or Enter", app.keys.add_to_my_team.to_ascii_uppercase()),
+                    Style::default().add_modifier(Modifier::BOLD),
+                ),
                 Span::raw(" to add to my team, "),
-                Span::styled("B", Style::default().add_modifier(Modifier::BOLD)),
-                Span::raw(" to add to other team,"),
+                Span::styled(
+                    app.keys.add_to_other_team.to_ascii_uppercase().to_string(),
+                    Style::default().add_modifier(Modifier::BOLD),
+                ),
+                Span::raw(" to add to other team, "),
+                Span::styled("O", Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(" to add past a full roster, "),
+                Span::styled("N", Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(" to write a note, "),
+                Span::styled("Esc", Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(" to go back"),
+            ],
+            Style::default(),
+        ),
+        InputMode::Board => (
+            vec![
+                Span::raw("Press "),
+                Span::styled("Esc, d or q", Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(" to go back to idle"),
+            ],
+            Style::default(),
+        ),
+        InputMode::Threshold => (
+            vec![
+                Span::raw("Type the "),
+                Span::styled(app.threshold.label().to_lowercase(), Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(", "),
+                Span::styled("Enter", Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(" to apply it (empty clears it), "),
+                Span::styled("Esc", Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(" to cancel"),
+            ],
+            Style::default(),
+        ),
+        InputMode::Noting => (
+            vec![
+                Span::raw("Type a note for "),
+                Span::styled(app.candidate_player.clone(), Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(", "),
+                Span::styled("Enter", Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(" to save it (empty removes it), "),
+                Span::styled("Esc", Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(" to cancel"),
+            ],
+            Style::default(),
+        ),
+        InputMode::Recommending => (
+            vec![
+                Span::raw("Press "),
+                Span::styled("Esc", Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(" to go back to idle, "),
+                Span::styled("Up/Down Arrows", Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(" to select player, "),
+                Span::styled("Enter", Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(" to pick the player"),
+            ],
+            Style::default(),
+        ),
+        InputMode::Comparing => (
+            vec![
+                Span::raw("Press "),
                 Span::styled("Esc", Style::default().add_modifier(Modifier::BOLD)),
-                Span::raw(" to go back to searching"),
+                Span::raw(" to clear the comparison and go back to searching"),
             ],
             Style::default(),
         ),
@@ -415,24 +3590,140 @@ fn ui<B: Backend>(f: &mut Frame<B>, app: &App) {
             vec![
                 Span::raw("Press "),
                 Span::styled("Q", Style::default().add_modifier(Modifier::BOLD)),
-                Span::raw(" to go back to idle "),
+                Span::raw(" to go back to idle, "),
+                Span::styled("Up/Down Arrows", Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(" to select a slot, "),
+                Span::styled("Shift+Up/Down", Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(" to reorder, "),
+                Span::styled("Tab", Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(" to switch teams, "),
+                Span::styled("D", Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(" to remove the player, "),
+                Span::styled("M", Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(" to move them to the other team, "),
+                Span::styled("O", Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(" to show the other team, "),
+                Span::styled("W", Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(" to show the watchlist, "),
+                Span::styled("H", Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(" to show excluded players, "),
+                Span::styled("E", Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(" to export to CSV, "),
+                Span::styled("X", Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(" to export a Markdown summary"),
             ],
             Style::default(),
-        )
+        ),
+        InputMode::PositionJump => (
+            vec![
+                Span::raw("Type "),
+                Span::styled("a, pg, sg, sf, pf, c, f, g, t, sh, u or b", Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(" to filter by position, anything else to cancel "),
+                Span::styled(app.jump_keys.clone(), Style::default().fg(Color::Yellow)),
+            ],
+            Style::default(),
+        ),
     };
+    // the note of the selected player takes the place of the key help
+    let selected_note = match app.input_mode {
+        InputMode::Searching | InputMode::Recommending => app
+            .selected_player
+            .and_then(|i| app.filtered_players.get(i))
+            .and_then(|name| app.notes.get(name)),
+        InputMode::Picking => app.notes.get(&app.candidate_player),
+        _ => None,
+    };
+    if let Some(note) = selected_note {
+        msg = vec![Span::styled(format!("Note: {}", note), Style::default().fg(Color::Magenta))];
+    }
+    if let Some(status) = &app.status_message {
+        msg = vec![Span::styled(status.clone(), Style::default().fg(Color::Green))];
+    }
+    let left = app.remaining_count(&app.selected_position);
+    msg.insert(
+        0,
+        Span::styled(
+            format!("{:?}: {} left | ", app.selected_position, left),
+            if left < SCARCITY_THRESHOLD {
+                Style::default().fg(Color::Red)
+            } else {
+                Style::default()
+            },
+        ),
+    );
+    if let Some(thresholds) = app.threshold_summary() {
+        msg.insert(0, Span::styled(format!("{} | ", thresholds), Style::default().fg(Color::Magenta)));
+    }
+    if matches!(app.input_mode, InputMode::Listing | InputMode::Searching) {
+        let warnings = app.roster_warnings();
+        if !warnings.is_empty() {
+            msg.insert(0, Span::styled(format!("{} | ", warnings.join(", ")), Style::default().fg(Color::Yellow)));
+        }
+    }
+    if let Some(secs) = app.clock_secs_left() {
+        let color = if secs < CLOCK_WARNING_SECS { Color::Red } else { Color::Cyan };
+        msg.insert(0, Span::styled(format!("{}s left | ", secs), Style::default().fg(color)));
+    }
+    msg.insert(
+        0,
+        Span::styled(
+            format!(
+                "Round {}, team {} on the clock{} | ",
+                app.draft.round(),
+                app.draft.on_the_clock() + 1,
+                if app.my_slot == Some(app.draft.on_the_clock()) { " (mine)" } else { "" }
+            ),
+            Style::default().fg(Color::Cyan),
+        ),
+    );
     let mut text = Text::from(Spans::from(msg));
     text.patch_style(style);
     let help_message = Paragraph::new(text);
     f.render_widget(help_message, chunks[0]);
 
-    let input = Paragraph::new(app.input.as_ref())
+    let (input_text, input_title) = if app.input_mode == InputMode::Noting {
+        (&app.note_input, Spans::from(format!("Note for {}", app.candidate_player)))
+    } else if app.input_mode == InputMode::Threshold {
+        (&app.threshold_input, Spans::from(app.threshold.label()))
+    } else {
+        let (team, tag, _) = parse_search_filters(&app.input);
+        let filters: Vec<String> = team
+            .map(|team| format!("team: {}", team.to_uppercase()))
+            .into_iter()
+            .chain(tag.map(|tag| format!("tag: {}", tag)))
+            .collect();
+        let mut title = vec![Span::raw(if filters.is_empty() {
+            "Input".to_string()
+        } else {
+            format!("Input ({})", filters.join(", "))
+        })];
+        if app.input_mode == InputMode::Searching {
+            title.push(match app.match_count {
+                0 => Span::styled(" \u{2014} no matches", Style::default().fg(Color::Red)),
+                1 => Span::raw(" \u{2014} 1 match"),
+                count => Span::raw(format!(" \u{2014} {} matches", count)),
+            });
+        }
+        (&app.input, Spans::from(title))
+    };
+    let mut input_spans = vec![Span::raw(input_text.as_str())];
+    if app.input_mode == InputMode::Searching {
+        input_spans.push(Span::styled(app.completion.as_str(), Style::default().fg(Color::DarkGray)));
+    }
+    let input = Paragraph::new(Spans::from(input_spans))
         .style(match app.input_mode {
             InputMode::Idle => Style::default(),
             InputMode::Searching => Style::default().fg(Color::Yellow),
             InputMode::Picking => Style::default().fg(Color::Blue),
             InputMode::Listing => Style::default().fg(Color::Red),
+            InputMode::PositionJump => Style::default(),
+            InputMode::Recommending => Style::default(),
+            InputMode::Comparing => Style::default(),
+            InputMode::Noting => Style::default().fg(Color::Magenta),
+            InputMode::Threshold => Style::default().fg(Color::Magenta),
+            InputMode::Board => Style::default(),
         })
-        .block(Block::default().borders(Borders::ALL).title("Input"));
+        .block(Block::default().borders(Borders::ALL).title(input_title));
     f.render_widget(input, chunks[1]);
     match app.input_mode {
         InputMode::Idle =>
@@ -442,138 +3733,1029 @@ fn ui<B: Backend>(f: &mut Frame<B>, app: &App) {
         InputMode::Searching => {
             // Make the cursor visible and ask tui-rs to put it at the specified coordinates after rendering
             f.set_cursor(
-                // Put cursor past the end of the input text
-                chunks[1].x + app.input.width() as u16 + 1,
+                // Put cursor past the end of the input text, but inside the block
+                (chunks[1].x + app.input.width() as u16 + 1)
+                    .min((chunks[1].x + chunks[1].width).saturating_sub(2)),
                 // Move one line down, from the border to the input line
                 chunks[1].y + 1,
             )
         }
         InputMode::Picking => {}
         InputMode::Listing => {}
+        InputMode::PositionJump => {}
+        InputMode::Recommending => {}
+        InputMode::Comparing => {}
+        InputMode::Board => {}
+        InputMode::Noting | InputMode::Threshold => {
+            f.set_cursor(
+                (chunks[1].x + input_text.width() as u16 + 1)
+                    .min((chunks[1].x + chunks[1].width).saturating_sub(2)),
+                chunks[1].y + 1,
+            )
+        }
     }
 
     let (player_set, title) = match app.input_mode {
-        InputMode::Idle => (&app.filtered_players, "Doing nothing"),
-        InputMode::Searching => (&app.filtered_players, "Searching players"),
-        InputMode::Picking => (&app.filtered_players, "Picking a player"),
-        InputMode::Listing => (&app.my_players, "My players"),
+        InputMode::Idle => (&app.filtered_players, "Doing nothing".to_string()),
+        InputMode::Searching => (
+            &app.filtered_players,
+            format!(
+                "Searching {} players (by {}, showing {})",
+                if app.show_drafted { "DRAFTED" } else { "undrafted" },
+                app.sort_mode.label(),
+                app.display_stat.label()
+            ),
+        ),
+        InputMode::Picking | InputMode::Noting => (&app.filtered_players, "Picking a player".to_string()),
+        InputMode::Listing if app.list_other_team => (&app.other_players, "Other players".to_string()),
+        InputMode::Listing => (&app.my_players, "My players".to_string()),
+        InputMode::PositionJump => (&app.filtered_players, "Jumping to a position".to_string()),
+        InputMode::Recommending => (
+            &app.filtered_players,
+            format!("Best available {:?} players", app.selected_position),
+        ),
+        InputMode::Comparing => (&app.comparison, "Comparing players".to_string()),
+        InputMode::Threshold => (&app.filtered_players, "Setting a threshold".to_string()),
+        InputMode::Board => (&app.filtered_players, "Draft board".to_string()),
     };
-    if app.input_mode != InputMode::Listing {
+    if app.input_mode == InputMode::Comparing {
+        let comparison = comparison_table(app).block(Block::default().borders(Borders::ALL).title(title));
+        f.render_widget(comparison, chunks[2]);
+    } else if app.input_mode == InputMode::Board {
+        let teams = app.draft.num_teams as u32;
+        let mut widths = vec![Constraint::Length(4)];
+        widths.extend((0..teams).map(|_| Constraint::Ratio(1, teams)));
+        let board = board_table(app)
+            .widths(&widths)
+            .block(Block::default().borders(Borders::ALL).title(title));
+        f.render_widget(board, chunks[2]);
+    } else if app.input_mode == InputMode::Idle {
+        let dashboard = remaining_table(app).block(Block::default().borders(Borders::ALL).title("Players left"));
+        f.render_widget(dashboard, chunks[2]);
+    } else if app.input_mode == InputMode::Searching && player_set.is_empty() {
+        // blank lines push the message down to the middle of the block
+        let mut lines = vec![Spans::from(""); (chunks[2].height.saturating_sub(3) / 2) as usize];
+        lines.push(Spans::from(empty_message(app)));
+        let message = Paragraph::new(lines)
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: true })
+            .block(Block::default().borders(Borders::ALL).title(title));
+        f.render_widget(message, chunks[2]);
+    } else if app.input_mode != InputMode::Listing {
+        let rank_width = if app.has_ranks { 5 } else { 0 };
+        let label_width = if app.accessible { "[SELECTED] ".len() } else { 0 };
+        // the name column gives way first when the list is too narrow, minus the borders
+        let name_width = (chunks[2].width as usize)
+            .saturating_sub(2 + rank_width + label_width + LIST_COLUMNS_WIDTH)
+            .clamp(MIN_NAME_WIDTH, NAME_WIDTH);
         let players: Vec<ListItem> = player_set
             .iter()
             .enumerate()
+            .skip(app.scroll_offset)
+            .take(app.result_limit)
             .map(|(i, m)| {
-                let player: &Player = app.get_player(m).unwrap();
-                let content = vec![Spans::from(Span::raw(format!("{}: {} {:?}", i + 1, player.name, player.position)))];
+                let row = i - app.scroll_offset + 1;
+                let player: &Player = match app.get_player(m) {
+                    Some(player) => player,
+                    None => {
+                        let content = vec![Spans::from(Span::raw(format!("{}: (unknown: {})", row, m)))];
+                        return ListItem::new(content).style(Style::default().fg(Color::DarkGray));
+                    }
+                };
+                let draft_percent_style = if player.draft_percent_value() > HIGH_DRAFT_PERCENT {
+                    Style::default().fg(Color::Green)
+                } else {
+                    Style::default()
+                };
+                let note_marker = if app.notes.contains_key(&player.name) { "*" } else { "" };
+                let watched = app.watchlist.contains(&player.name);
+                let watch_marker = if watched { " [W]" } else { "" };
+                let tags: String = player.tags.iter().map(|tag| format!(" [{}]", tag_badge(tag))).collect();
+                let markers = format!("{}{}{}", note_marker, watch_marker, tags);
+                let name = truncate_to_width(
+                    &player.name,
+                    name_width.saturating_sub(markers.width()).max(MIN_NAME_WIDTH),
+                );
+                let padding = name_width.saturating_sub(name.width() + markers.width());
+                let mut spans = vec![Span::raw(format!("{:>2}: ", row))];
+                if app.accessible {
+                    let label = if Some(i) == app.selected_player { "[SELECTED] " } else { app.adp_label(player) };
+                    spans.push(Span::raw(pad_to_width(label, label_width)));
+                }
+                if app.has_ranks {
+                    let rank = player.rank.map_or(String::new(), |rank| format!("#{}", rank));
+                    spans.push(Span::styled(format!("{:<5}", rank), Style::default().fg(Color::DarkGray)));
+                }
+                spans.extend(highlight_matches(&name, app.match_positions.get(&player.name)));
+                let games = player.games.map_or("-".to_string(), |games| games.to_string());
+                let stat_style = if app.display_stat == DisplayStat::DraftPercent {
+                    draft_percent_style
+                } else {
+                    Style::default()
+                };
+                spans.push(Span::raw(format!("{}{} ", markers, " ".repeat(padding))));
+                // the selected row keeps a single color so the selection stands out
+                let position_style = match player.position.first() {
+                    Some(position) if Some(i) != app.selected_player => Style::default().fg(position.color()),
+                    _ => Style::default(),
+                };
+                spans.push(Span::styled(pad_to_width(&player.position_label(), 10), position_style));
+                spans.push(Span::raw(" "));
+                let stat = truncate_to_width(&app.display_stat.value(player), STAT_WIDTH);
+                let stat = format!("{}{}", " ".repeat(STAT_WIDTH.saturating_sub(stat.width())), stat);
+                spans.push(Span::styled(stat, stat_style));
+                spans.push(Span::raw(format!(" {:>3} ", games)));
+                // the draft percentage is already shown as the stat
+                if app.display_stat != DisplayStat::DraftPercent {
+                    spans.push(Span::styled(player.draft_percent.clone(), draft_percent_style));
+                }
+                let tier = app.tiers.get(&player.name);
+                if let Some(tier) = tier {
+                    spans.push(Span::styled(format!(" T{}", tier), Style::default().fg(Color::DarkGray)));
+                }
+                if app.show_teammates {
+                    let teammates = app.teammates_drafted(player).len();
+                    if teammates > 0 {
+                        spans.push(Span::styled(
+                            format!(" ({} {} teammate{})", teammates, player.team, if teammates == 1 { "" } else { "s" }),
+                            Style::default().fg(Color::Yellow),
+                        ));
+                    }
+                }
+                let content = vec![Spans::from(spans)];
+                // underline the last player of every tier, as long as the list is in tier order
+                let ends_tier = app.sort_mode == SortMode::PickAvg
+                    && app.input.is_empty()
+                    && player_set
+                        .get(i + 1)
+                        .is_some_and(|next| app.tiers.get(next) != tier);
                 let color = match app.input_mode {
-                    InputMode::Idle | InputMode::Listing => Color::Reset,
-                    InputMode::Searching => {
+                    InputMode::Idle
+                    | InputMode::Listing
+                    | InputMode::PositionJump
+                    | InputMode::Comparing
+                    | InputMode::Threshold
+                    | InputMode::Board => Color::Reset,
+                    InputMode::Searching | InputMode::Recommending => {
                         if Some(i) == app.selected_player {
                             Color::Yellow
+                        } else if watched {
+                            Color::Cyan
                         } else {
-                            Color::Reset
+                            app.adp_color(player)
                         }
                     }
-                    InputMode::Picking => {
+                    InputMode::Picking | InputMode::Noting => {
                         if Some(i) == app.selected_player {
                             Color::Blue
+                        } else if watched {
+                            Color::Cyan
                         } else {
-                            Color::Reset
+                            app.adp_color(player)
                         }
                     }
                 };
-                ListItem::new(content).style(Style::default().fg(color))
-                
+                let style = Style::default().fg(color);
+                if ends_tier {
+                    ListItem::new(content).style(style.add_modifier(Modifier::UNDERLINED))
+                } else {
+                    ListItem::new(content).style(style)
+                }
             })
             .collect();
 
         let players = List::new(players).block(Block::default().borders(Borders::ALL).title(title));
 
-        f.render_widget(players, chunks[2]);
-    } else {
-        let slots = App::slots();
-        let mut filled_slots: Vec<(Position, String, Vec<Position>)> = Vec::new();
+        if app.input_mode == InputMode::Recommending {
+            let recommending_chunks = Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints([Constraint::Percentage(50), Constraint::Percentage(50)].as_ref())
+                .split(chunks[2]);
+            // only the left list can be clicked
+            app.list_area = recommending_chunks[0];
 
-        for (position, slot) in slots.iter() {
-            let mut slots_left = slot.clone();
-            for player in app.my_players.iter() {
-                let player: &Player = app.get_player(player).unwrap();
-                if  filled_slots.iter().find(|x| x.1 == player.name).is_none() &&
-                    player.position.iter().any(|p| p.does_position_belong(position)) {
-                    if slots_left > 0 {
-                        filled_slots.push((position.clone(), player.name.clone(), player.position.clone()));
-                        slots_left -= 1;
-                    }
-                }
-                if slots_left == 0 {
-                    break;
-                }
-            }
-            while slots_left > 0 {
-                filled_slots.push((position.clone(), "Empty".to_string(), vec![]));
-                slots_left -= 1;
-            }
+            let by_need = List::new(need_items(app, &app.best_available_by_need(BEST_AVAILABLE_LIMIT)))
+                .block(Block::default().borders(Borders::ALL).title("Best for my needs"));
+
+            f.render_widget(players, recommending_chunks[0]);
+            f.render_widget(by_need, recommending_chunks[1]);
+        } else if matches!(app.input_mode, InputMode::Picking | InputMode::Noting) {
+            let picking_chunks = Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints([Constraint::Percentage(50), Constraint::Percentage(50)].as_ref())
+                .split(chunks[2]);
+
+            // my roster as it would be after picking the candidate
+            let mut preview_players = app.my_players.clone();
+            preview_players.push(app.candidate_player.clone());
+            let preview_slot = app.preview_slot(&app.candidate_player);
+            let fills: Vec<String> = app
+                .eligible_open_slots(&app.candidate_player)
+                .iter()
+                .map(|slot| format!("{:?}", slot))
+                .collect();
+            let preview_block = match preview_slot {
+                Some(_) if fills.is_empty() => Block::default()
+                    .borders(Borders::ALL)
+                    .title(format!("If you pick {}", app.candidate_player)),
+                Some(_) => Block::default()
+                    .borders(Borders::ALL)
+                    .title(format!("If you pick {} (fills: {})", app.candidate_player, fills.join(", "))),
+                None => Block::default()
+                    .borders(Borders::ALL)
+                    .title(Span::styled("No open slot for this position", Style::default().fg(Color::Red))),
+            };
+            let preview = List::new(slot_items(app, &preview_players, preview_slot)).block(preview_block);
+
+            f.render_widget(players, picking_chunks[0]);
+            // scrolls the preview so the candidate's slot stays visible
+            let mut preview_state = ListState::default();
+            preview_state.select(preview_slot);
+            f.render_stateful_widget(preview, picking_chunks[1], &mut preview_state);
+        } else {
+            f.render_widget(players, chunks[2]);
         }
+    } else {
+        let listing_chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(2), Constraint::Length(1), Constraint::Min(1)].as_ref())
+            .split(chunks[2]);
 
-        let players: Vec<ListItem> = filled_slots
-            .iter()
-            .map(|(position, name, player_position)| {
-                let content = vec![Spans::from(Span::raw(format!("{:?}: {} {:?}", position, name, player_position)))];
-                let color = if name == "Empty" {
-                    Color::Red
+        // roster fill status by position, e.g. "C 2/3  PG 0/1", colored by how full it is
+        let summary: Vec<Span> = app
+            .slot_fill()
+            .into_iter()
+            .map(|(position, filled, total)| {
+                let color = if filled == total {
+                    Color::Green
+                } else if filled > 0 {
+                    Color::Yellow
                 } else {
-                    if player_position.len() == 1 {
-                        Color::Green
-                    } else {
-                        Color::Yellow
-                    }
+                    Color::Red
                 };
-                ListItem::new(content).style(Style::default().fg(color))
-                
+                Span::styled(format!("{:?} {}/{}  ", position, filled, total), Style::default().fg(color))
             })
             .collect();
 
-        let players = List::new(players).block(Block::default().borders(Borders::ALL).title(title));
+        let roster_size: u16 = app.slots().iter().map(|(_, count)| count).sum();
+        let mut value = format!(
+            "Avg pick sum: {:.1}  Drafted: {}/{}",
+            app.roster_pick_value(),
+            app.my_players.len(),
+            roster_size
+        );
+        if app.side_by_side {
+            value.push_str(&format!(
+                "  Other team: {:.1}  Drafted: {}/{}",
+                app.pick_value(&app.other_players),
+                app.other_players.len(),
+                roster_size
+            ));
+        } else if !app.compare_players.is_empty() {
+            value.push_str(&format!(
+                "  Compared team: {:.1}  Drafted: {}/{}",
+                app.pick_value(&app.compare_players),
+                app.compare_players.len(),
+                roster_size
+            ));
+        }
+
+        let header = vec![Spans::from(summary), Spans::from(Span::raw(value))];
+        f.render_widget(Paragraph::new(header), listing_chunks[0]);
+
+        let filled = app.my_players.len().min(roster_size as usize);
+        let ratio = if roster_size == 0 { 0.0 } else { filled as f64 / roster_size as f64 };
+        let gauge = Gauge::default()
+            .gauge_style(Style::default().fg(Color::Green).bg(Color::Black))
+            .ratio(ratio)
+            .label(format!("{}/{} slots ({:.0}%)", filled, roster_size, ratio * 100.0));
+        f.render_widget(gauge, listing_chunks[1]);
 
-        f.render_widget(players, chunks[2]);
+        let roster_chunks = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Min(1), Constraint::Length(16)].as_ref())
+            .split(listing_chunks[2]);
+        let teams = List::new(team_items(app))
+            .block(Block::default().borders(Borders::ALL).title("NBA teams"));
+        f.render_widget(teams, roster_chunks[1]);
+
+        let listed_players = List::new(slot_items(app, player_set, Some(app.selected_slot)))
+            .block(Block::default().borders(Borders::ALL).title(title));
+
+        if app.show_excluded {
+            let excluded = List::new(excluded_items(app))
+                .block(Block::default().borders(Borders::ALL).title("Excluded players (D to un-exclude)"));
+            f.render_widget(excluded, roster_chunks[0]);
+        } else if app.show_watchlist {
+            let watchlist = List::new(watchlist_items(app))
+                .block(Block::default().borders(Borders::ALL).title("Watchlist (D to un-watch)"));
+            f.render_widget(watchlist, roster_chunks[0]);
+        } else if app.side_by_side {
+            let team_chunks = Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints([Constraint::Percentage(50), Constraint::Percentage(50)].as_ref())
+                .split(roster_chunks[0]);
+
+            // the team that isn't listed goes on the right
+            let (players, title) = if app.list_other_team {
+                (&app.my_players, "My players")
+            } else {
+                (&app.other_players, "Other players")
+            };
+            let other_players = List::new(slot_items(app, players, None))
+                .block(Block::default().borders(Borders::ALL).title(title));
+
+            f.render_widget(listed_players, team_chunks[0]);
+            f.render_widget(other_players, team_chunks[1]);
+        } else if !app.compare_players.is_empty() {
+            let team_chunks = Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints([Constraint::Percentage(50), Constraint::Percentage(50)].as_ref())
+                .split(roster_chunks[0]);
+
+            let compare_players = List::new(slot_items(app, &app.compare_players, None))
+                .block(Block::default().borders(Borders::ALL).title("Compared team"));
+
+            f.render_widget(listed_players, team_chunks[0]);
+            f.render_widget(compare_players, team_chunks[1]);
+        } else {
+            f.render_widget(listed_players, roster_chunks[0]);
+        }
     }
     
     
 
 
-    // split chunks[3] into 10 chunks, one for each position
-    let position_chunks = Layout::default()
+    if app.show_position_picker {
+        // split chunks[3] into one chunk for each position
+        let positions = Position::get_all_positions().len() as u32;
+        let position_chunks = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints(vec![Constraint::Ratio(1, positions); positions as usize])
+            .split(chunks[3]);
+
+        for (i, position) in Position::get_all_positions().iter().enumerate() {
+            let style = if app.selected_position == *position {
+                Style::default().fg(Color::Yellow)
+            } else {
+                Style::default()
+            };
+
+            let left = app.remaining_count(position);
+            let left_style = if left < SCARCITY_THRESHOLD {
+                Style::default().fg(Color::Red)
+            } else {
+                Style::default()
+            };
+            let widget = Paragraph::new(Spans::from(vec![
+                Span::styled(format!("{:?} ", position), style),
+                Span::styled(left.to_string(), left_style),
+            ]))
+                .block(Block::default().borders(Borders::ALL)
+                .title("Pos")
+            );
+            f.render_widget(widget, position_chunks[i]);
+        }
+    } else {
+        let left = app.remaining_count(&app.selected_position);
+        let left_style = if left < SCARCITY_THRESHOLD {
+            Style::default().fg(Color::Red)
+        } else {
+            Style::default()
+        };
+        let filter = Paragraph::new(Spans::from(vec![
+            Span::raw("Filter: "),
+            Span::styled(format!("{:?} ", app.selected_position), Style::default().fg(Color::Yellow)),
+            Span::styled(format!("({} left)", left), left_style),
+        ]));
+        f.render_widget(filter, chunks[3]);
+    }
+
+    // most recent pick first, whatever doesn't fit is cut off
+    let mut ticker = vec![Span::styled("Recent picks: ", Style::default().fg(Color::DarkGray))];
+    ticker.extend(
+        app.recent_picks
+            .iter()
+            .rev()
+            .map(|pick| Span::raw(format!("{}  ", pick))),
+    );
+    f.render_widget(Paragraph::new(Spans::from(ticker)), chunks[4]);
+
+    let stats = app.pool_stats();
+    let by_position: Vec<String> = stats
+        .by_position
+        .iter()
+        .map(|(position, count)| format!("{:?} {}", position, count))
+        .collect();
+    let footer = format!(
+        "Pool: {} left, avg pick {:.1} | {}",
+        stats.count,
+        stats.avg_pick,
+        by_position.join("  ")
+    );
+    f.render_widget(Paragraph::new(Span::styled(footer, Style::default().fg(Color::DarkGray))), chunks[5]);
+
+    if app.show_help {
+        let area = centered_rect(80, 80, f.size());
+        let block = Block::default().borders(Borders::ALL).title("Keys (? or Esc to close)");
+        let columns = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)].as_ref())
+            .split(block.inner(area));
+        let (left, right) = help_lines(&app.keys);
+        f.render_widget(Clear, area);
+        f.render_widget(block, area);
+        f.render_widget(Paragraph::new(left).wrap(Wrap { trim: false }), columns[0]);
+        f.render_widget(Paragraph::new(right).wrap(Wrap { trim: false }), columns[1]);
+    }
+
+    if let Some(player) = app.detail_player.as_ref().and_then(|name| app.get_player(name)) {
+        let area = centered_rect(60, 60, f.size());
+        let card = Paragraph::new(detail_lines(app, player))
+            .wrap(Wrap { trim: false })
+            .block(Block::default().borders(Borders::ALL).title("Player (Esc to close)"));
+        f.render_widget(Clear, area);
+        f.render_widget(card, area);
+    }
+}
+
+/// A rectangle of the given percentage of `r`'s size, centered in it.
+fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
+    let vertical = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(
+            [
+                Constraint::Percentage((100 - percent_y) / 2),
+                Constraint::Percentage(percent_y),
+                Constraint::Percentage((100 - percent_y) / 2),
+            ]
+            .as_ref(),
+        )
+        .split(r);
+
+    Layout::default()
         .direction(Direction::Horizontal)
         .constraints(
             [
-                Constraint::Percentage(10),
-                Constraint::Percentage(10),
-                Constraint::Percentage(10),
-                Constraint::Percentage(10),
-                Constraint::Percentage(10),
-                Constraint::Percentage(10),
-                Constraint::Percentage(10),
-                Constraint::Percentage(10),
-                Constraint::Percentage(10),
-                Constraint::Percentage(10),
+                Constraint::Percentage((100 - percent_x) / 2),
+                Constraint::Percentage(percent_x),
+                Constraint::Percentage((100 - percent_x) / 2),
             ]
             .as_ref(),
         )
-        .split(chunks[3]);
+        .split(vertical[1])[1]
+}
+
+/// Everything known about the player, for the detail card.
+fn detail_lines(app: &App, player: &Player) -> Vec<Spans<'static>> {
+    let bold = Style::default().add_modifier(Modifier::BOLD);
+    let field = |label: &str, value: String| {
+        Spans::from(vec![Span::styled(format!("{:<14}", label), bold), Span::raw(value)])
+    };
+
+    // every position filter the player shows up under
+    let eligible = Position::get_all_positions()
+        .into_iter()
+        .filter(|group| *group != Position::ANY && player.eligible_for(group))
+        .map(|group| format!("{:?}", group))
+        .collect::<Vec<String>>()
+        .join(", ");
+    let teammates = app
+        .teammates_drafted(player)
+        .iter()
+        .map(|p| p.name.clone())
+        .collect::<Vec<String>>();
+
+    vec![
+        Spans::from(Span::styled(player.name.clone(), bold.fg(Color::Yellow))),
+        Spans::from(""),
+        field("Team", player.team.clone()),
+        field("Position", player.position_label()),
+        field("Eligible for", eligible),
+        field("Pick avg", format!("{:.1}", player.pick_avg)),
+        field("Round avg", format!("{:.1}", player.round_avg)),
+        field("Draft %", player.draft_percent.clone()),
+        field("Games", player.games.map_or("-".to_string(), |games| games.to_string())),
+        field("Note", app.notes.get(&player.name).cloned().unwrap_or_else(|| "-".to_string())),
+        field(
+            "Teammates",
+            if teammates.is_empty() { "none drafted".to_string() } else { teammates.join(", ") },
+        ),
+    ]
+}
+
+/// Every keybinding grouped by mode, see `KEYBINDINGS`, split into two
+/// columns of about the same height.
+fn help_lines(keys: &KeyBindings) -> (Vec<Spans<'static>>, Vec<Spans<'static>>) {
+    let total: usize = KEYBINDINGS.iter().map(|(_, keys)| keys.len() + 1).sum();
+    let mut left: Vec<Spans> = Vec::new();
+    let mut right: Vec<Spans> = Vec::new();
+    for (mode, keys) in KEYBINDINGS.iter() {
+        let lines = if left.len() < total / 2 { &mut left } else { &mut right };
+        lines.push(Spans::from(Span::styled(
+            mode.to_string(),
+            Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD),
+        )));
+        for (key, action) in keys.iter() {
+            lines.push(Spans::from(vec![
+                Span::styled(format!("  {:<12} ", key), Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(action.to_string()),
+            ]));
+        }
+    }
+
+    // the table lists the default keys, point out the ones keys.toml changed
+    if *keys != KeyBindings::default() {
+        right.push(Spans::from(Span::styled(
+            "Remapped in keys.toml",
+            Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD),
+        )));
+        let remapped = [
+            ("search", keys.search),
+            ("add to my team", keys.add_to_my_team),
+            ("add to the other team", keys.add_to_other_team),
+            ("list", keys.list),
+            ("quit", keys.quit),
+        ];
+        for (action, key) in remapped.iter() {
+            right.push(Spans::from(vec![
+                Span::styled(format!("  {:<12} ", key), Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(action.to_string()),
+            ]));
+        }
+    }
+
+    (left, right)
+}
+
+/// Rows of the roster slot list for the given team, with the selected slot
+/// highlighted.
+fn slot_items(app: &App, players: &[String], selected_slot: Option<usize>) -> Vec<ListItem<'static>> {
+    let mut items: Vec<ListItem> = app
+        .fill_slots(players)
+        .iter()
+        .enumerate()
+        .map(|(i, (position, name, player_position, _))| {
+            let round = match app.rounds.get(name) {
+                Some(round) => format!(" R{}", round),
+                None => String::new(),
+            };
+            let keeper = if app.keepers.contains(name) { " [K]" } else { "" };
+            let (color, status) = if name == "Empty" {
+                (Color::Red, "[EMPTY] ")
+            } else if player_position.len() == 1 {
+                (Color::Green, "[FILLED] ")
+            } else {
+                (Color::Yellow, "[FLEX] ")
+            };
+            let content = vec![Spans::from(Span::raw(format!(
+                "{}{:?}: {}{} {}{}",
+                if app.accessible { status } else { "" },
+                position,
+                name,
+                keeper,
+                Position::label(player_position),
+                round
+            )))];
+            let style = if Some(i) == selected_slot {
+                Style::default().fg(color).add_modifier(Modifier::REVERSED)
+            } else {
+                Style::default().fg(color)
+            };
+            ListItem::new(content).style(style)
+        })
+        .collect();
 
-    for (i, position) in Position::get_all_positions().iter().enumerate() {
-        let style = if app.selected_position == *position {
-            Style::default().fg(Color::Yellow)
+    // players that can't be placed in a slot are still shown, greyed out
+    for name in players.iter().filter(|p| app.get_player(p).is_none()) {
+        let content = vec![Spans::from(Span::raw(format!("(unknown: {})", name)))];
+        let style = if Some(items.len()) == selected_slot {
+            Style::default().fg(Color::DarkGray).add_modifier(Modifier::REVERSED)
         } else {
-            Style::default()
+            Style::default().fg(Color::DarkGray)
         };
+        items.push(ListItem::new(content).style(style));
+    }
 
-        let widget = Paragraph::new(format!("{:?}", position))
-            .style(style)
-            .block(Block::default().borders(Borders::ALL)
-            .title("Pos")
-        );
-        f.render_widget(widget, position_chunks[i]);
+    items
+}
+
+/// Rows of the best available players by need, with the ones that fill an
+/// open slot in green.
+fn need_items(app: &App, players: &[String]) -> Vec<ListItem<'static>> {
+    let open_slots = app.open_slots();
+
+    players
+        .iter()
+        .filter_map(|name| app.get_player(name))
+        .map(|player| {
+            let text = format!("{} {} {}", player.name, player.position_label(), player.pick_avg);
+            if app.fills_need(player, &open_slots) {
+                ListItem::new(format!("{} (need)", text)).style(Style::default().fg(Color::Green))
+            } else {
+                ListItem::new(text)
+            }
+        })
+        .collect()
+}
+
+/// Rows of the NBA team breakdown of my roster, with teams I have too many
+/// players from in yellow.
+fn team_items(app: &App) -> Vec<ListItem<'static>> {
+    app.team_distribution()
+        .into_iter()
+        .map(|(team, count)| {
+            let style = if count >= TEAM_CONCENTRATION_WARNING {
+                Style::default().fg(Color::Yellow)
+            } else {
+                Style::default()
+            };
+            ListItem::new(format!("{} {}", team, count)).style(style)
+        })
+        .collect()
+}
+
+/// Rows of the excluded players, with the selected one highlighted.
+fn excluded_items(app: &App) -> Vec<ListItem<'static>> {
+    app.excluded
+        .iter()
+        .enumerate()
+        .map(|(i, name)| {
+            let text = match app.get_player(name) {
+                Some(player) => format!("{} {} {}", player.name, player.position_label(), player.team),
+                None => format!("(unknown: {})", name),
+            };
+            let style = if i == app.selected_slot {
+                Style::default().add_modifier(Modifier::REVERSED)
+            } else {
+                Style::default().fg(Color::DarkGray)
+            };
+            ListItem::new(text).style(style)
+        })
+        .collect()
+}
+
+/// Rows of the watchlist, with drafted players greyed out.
+fn watchlist_items(app: &App) -> Vec<ListItem<'static>> {
+    app.watchlist
+        .iter()
+        .enumerate()
+        .map(|(i, name)| {
+            let (text, color) = match app.get_player(name) {
+                Some(player) => (
+                    format!("{} {} {}", player.name, player.position_label(), player.draft_percent),
+                    Color::Cyan,
+                ),
+                None => (format!("(unknown: {})", name), Color::DarkGray),
+            };
+            let (text, color) = if app.my_players.contains(name) || app.other_players.contains(name) {
+                (format!("{} (drafted)", text), Color::DarkGray)
+            } else {
+                (text, color)
+            };
+            let style = if i == app.selected_slot {
+                Style::default().add_modifier(Modifier::REVERSED)
+            } else {
+                Style::default().fg(color)
+            };
+            ListItem::new(text).style(style)
+        })
+        .collect()
+}
+
+/// The picks of the draft with a row per round and a column per team, with
+/// the pick on the clock highlighted. The caller sets the column widths.
+fn board_table(app: &App) -> Table<'static> {
+    let teams = app.draft.num_teams;
+    let rounds = app.draft.round();
+    let mut board: Vec<Vec<String>> = vec![vec![String::new(); teams]; rounds];
+    for (pick, (team, name)) in app.draft.picks.iter().enumerate() {
+        if let Some(cell) = board.get_mut(pick / teams).and_then(|round| round.get_mut(*team)) {
+            *cell = name.clone();
+        }
+    }
+
+    let rows: Vec<Row> = board
+        .into_iter()
+        .enumerate()
+        .map(|(round, picks)| {
+            let mut cells = vec![Cell::from(format!("R{}", round + 1))];
+            cells.extend(picks.into_iter().enumerate().map(|(team, name)| {
+                if round + 1 == app.draft.round() && team == app.draft.on_the_clock() {
+                    Cell::from(name).style(Style::default().bg(Color::Yellow).fg(Color::Black))
+                } else {
+                    Cell::from(name)
+                }
+            }));
+            Row::new(cells)
+        })
+        .collect();
+
+    let mut header = vec!["".to_string()];
+    header.extend((1..=teams).map(|team| format!("Team {}", team)));
+    Table::new(rows).header(Row::new(header).style(Style::default().add_modifier(Modifier::BOLD)))
+}
+
+/// Undrafted players left at each of the five basic positions, with scarce
+/// positions in red.
+fn remaining_table(app: &App) -> Table<'static> {
+    let rows: Vec<Row> = [Position::PG, Position::SG, Position::SF, Position::PF, Position::C]
+        .iter()
+        .map(|position| {
+            let left = app.remaining_count(position);
+            let style = if left < SCARCITY_THRESHOLD {
+                Style::default().fg(Color::Red)
+            } else {
+                Style::default()
+            };
+            Row::new(vec![Cell::from(format!("{:?}", position)), Cell::from(left.to_string())]).style(style)
+        })
+        .collect();
+
+    Table::new(rows)
+        .header(Row::new(vec!["Position", "Left"]).style(Style::default().add_modifier(Modifier::BOLD)))
+        .widths(&[Constraint::Length(10), Constraint::Length(6)])
+}
+
+/// Stats of the two compared players next to each other, with the better
+/// value of every comparable stat in green.
+fn comparison_table(app: &App) -> Table<'static> {
+    let players: Vec<&Player> = app.comparison.iter().filter_map(|p| app.get_player(p)).collect();
+    if players.len() < 2 {
+        return Table::new(vec![]).widths(&[]);
+    }
+    let (a, b) = (players[0], players[1]);
+
+    // which player is better: Some(true) for the first, Some(false) for the second
+    let lower = |x: f32, y: f32| if x == y { None } else { Some(x < y) };
+    let higher = |x: f32, y: f32| if x == y { None } else { Some(x > y) };
+
+    let stat_row = |label: &str, first: String, second: String, better: Option<bool>| {
+        let style = |is_better: bool| {
+            if is_better {
+                Style::default().fg(Color::Green)
+            } else {
+                Style::default()
+            }
+        };
+        Row::new(vec![
+            Cell::from(label.to_string()),
+            Cell::from(first).style(style(better == Some(true))),
+            Cell::from(second).style(style(better == Some(false))),
+        ])
     };
-    
-}
\ No newline at end of file
+
+    let mut rows = vec![
+        stat_row("Team", a.team.clone(), b.team.clone(), None),
+        stat_row("Position", a.position_label(), b.position_label(), None),
+        stat_row("Pick avg", a.pick_avg.to_string(), b.pick_avg.to_string(), lower(a.pick_avg, b.pick_avg)),
+        stat_row("Round avg", a.round_avg.to_string(), b.round_avg.to_string(), lower(a.round_avg, b.round_avg)),
+        stat_row(
+            "Draft %",
+            a.draft_percent.clone(),
+            b.draft_percent.clone(),
+            higher(a.draft_percent_value(), b.draft_percent_value()),
+        ),
+    ];
+    if a.games.is_some() || b.games.is_some() {
+        let games = |p: &Player| p.games.map_or("-".to_string(), |games| games.to_string());
+        let better = match (a.games, b.games) {
+            (Some(x), Some(y)) if x != y => Some(x > y),
+            _ => None,
+        };
+        rows.push(stat_row("Games", games(a), games(b), better));
+    }
+
+    Table::new(rows)
+        .header(
+            Row::new(vec![String::new(), a.name.clone(), b.name.clone()])
+                .style(Style::default().add_modifier(Modifier::BOLD)),
+        )
+        .widths(&[Constraint::Length(12), Constraint::Percentage(40), Constraint::Percentage(40)])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn player(name: &str, team: &str, position: Vec<Position>, pick_avg: f32) -> Player {
+        Player {
+            name: name.to_string(),
+            team: team.to_string(),
+            position,
+            pick_avg,
+            round_avg: pick_avg / 12.0 + 1.0,
+            draft_percent: "90%".to_string(),
+            games: None,
+            rank: None,
+            tags: Vec::new(),
+        }
+    }
+
+    fn app(players: Vec<Player>) -> App {
+        App {
+            normalized_names: players.iter().map(|p| normalize(&p.name)).collect(),
+            all_players: players,
+            input_mode: InputMode::Searching,
+            ..App::default()
+        }
+    }
+
+    fn league() -> Vec<Player> {
+        let mut players = vec![
+            player("James Harden", "LAC", vec![Position::PG, Position::SG], 20.0),
+            player("LeBron James", "LAL", vec![Position::SF, Position::PF], 10.0),
+            player("Jamal Murray", "DEN", vec![Position::PG], 40.0),
+            player("Anthony Davis", "LAL", vec![Position::PF, Position::C], 8.0),
+            player("Nikola Jokić", "DEN", vec![Position::C], 1.0),
+            player("Jalen Brunson", "NYK", vec![Position::PG], 30.0),
+            player("Austin Reaves", "LAL", vec![Position::SG], 90.0),
+        ];
+        players[2].tags = vec!["injured".to_string()];
+        players[6].tags = vec!["injured".to_string()];
+        players
+    }
+
+    #[test]
+    fn narrowing_the_search_matches_a_full_scan() {
+        let steps: &[(&str, Position)] = &[
+            ("j", Position::ANY),
+            ("ja", Position::ANY),
+            ("jam", Position::ANY),
+            // the position changes between keystrokes
+            ("jam", Position::PG),
+            ("jama", Position::PG),
+            ("", Position::ANY),
+            ("@lal", Position::ANY),
+            ("@lal ", Position::ANY),
+            ("@lal a", Position::ANY),
+            ("@lal an", Position::ANY),
+            ("@la", Position::ANY),
+            ("#injured", Position::ANY),
+            ("#injured j", Position::ANY),
+            ("#injured ja", Position::ANY),
+            ("#injured jam", Position::ANY),
+        ];
+        let mut typed = app(league());
+        for (input, position) in steps {
+            if typed.selected_position != *position {
+                typed.set_position(position.clone());
+            }
+            typed.input = input.to_string();
+            typed.filter_players();
+
+            let mut scanned = app(league());
+            scanned.selected_position = position.clone();
+            scanned.input = input.to_string();
+            scanned.filter_players();
+
+            assert_eq!(typed.filtered_players, scanned.filtered_players, "after {:?}", input);
+            assert_eq!(typed.match_positions, scanned.match_positions, "after {:?}", input);
+            assert_eq!(typed.completion, scanned.completion, "after {:?}", input);
+        }
+    }
+
+    #[test]
+    fn shrinking_the_list_keeps_the_selection_in_range() {
+        let mut app = app(league());
+        app.filter_players();
+        app.selected_player = Some(app.filtered_players.len() - 1);
+
+        for c in "jam".chars() {
+            app.input.push(c);
+            app.filter_players();
+            let selected = app.selected_player.unwrap();
+            assert!(selected < app.filtered_players.len());
+        }
+
+        app.input = "nobody".to_string();
+        app.filter_players();
+        assert_eq!(app.selected_player, None);
+    }
+
+    #[test]
+    fn players_without_a_position_only_fit_open_slots() {
+        let mut players = league();
+        players.push(player("Mystery Man", "NYK", vec![], 50.0));
+        let mut app = app(players);
+        let mystery = app.all_players.last().unwrap().clone();
+
+        for position in [Position::ANY, Position::UTIL, Position::BENCH] {
+            assert!(mystery.eligible_for(&position), "{:?}", position);
+            app.set_position(position);
+            assert!(app.filtered_players.contains(&mystery.name));
+        }
+        for position in [Position::PG, Position::G, Position::C, Position::TALL] {
+            assert!(!mystery.eligible_for(&position), "{:?}", position);
+            app.set_position(position);
+            assert!(!app.filtered_players.contains(&mystery.name));
+        }
+
+        app.slots = vec![(Position::PG, 1), (Position::UTIL, 1), (Position::BENCH, 1)];
+        let roster = vec!["Mystery Man".to_string(), "Jalen Brunson".to_string()];
+        let rows = app.fill_slots(&roster);
+        assert_eq!(rows[0], (Position::PG, "Jalen Brunson".to_string(), vec![Position::PG], Some(1)));
+        assert_eq!(rows[1], (Position::UTIL, "Mystery Man".to_string(), vec![], Some(0)));
+        assert_eq!(rows[2].1, "Empty");
+
+        // with no open slot left they are added to the bench
+        app.slots = vec![(Position::PG, 1)];
+        let rows = app.fill_slots(&roster);
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[1], (Position::BENCH, "Mystery Man".to_string(), vec![], Some(0)));
+    }
+
+    #[test]
+    fn normalized_names_match_like_normalizing_every_name() {
+        let players: Vec<Player> = (0..5_000)
+            .map(|i| player(&format!("Nikola Jokić {}", i), "DEN", vec![Position::C], i as f32))
+            .collect();
+        let app = app(players);
+        for query in ["j", "jo", "jok", "joki", "jokic", "jokic 4"].map(normalize) {
+            // what every keystroke did before the names were normalized at load
+            let renormalized: Vec<Option<(i32, Vec<usize>)>> = app
+                .all_players
+                .iter()
+                .map(|p| app.fuzzy_score(&query, &normalize(&p.name)))
+                .collect();
+            let cached: Vec<Option<(i32, Vec<usize>)>> = app
+                .normalized_names
+                .iter()
+                .map(|name| app.fuzzy_score(&query, name))
+                .collect();
+            assert_eq!(renormalized, cached, "for {:?}", query);
+        }
+    }
+
+    #[test]
+    fn unknown_players_get_their_own_rows() {
+        let mut app = app(league());
+        app.slots = vec![(Position::PG, 1), (Position::C, 1)];
+        app.my_players = vec!["Gone Player".to_string(), "Jalen Brunson".to_string()];
+
+        let rows = app.roster_rows();
+        assert_eq!(app.listing_len(), 3);
+        assert_eq!(rows[0], ("Jalen Brunson".to_string(), Some(1)));
+        assert_eq!(rows[1], ("Empty".to_string(), None));
+        assert_eq!(rows[2], ("Gone Player".to_string(), Some(0)));
+    }
+
+    #[test]
+    fn players_on_both_rosters_stay_mine() {
+        let mut app = app(league());
+        app.my_players = vec!["LeBron James".to_string(), "Anthony Davis".to_string()];
+        app.other_players = vec!["Jamal Murray".to_string(), "Anthony Davis".to_string()];
+
+        assert_eq!(app.reconcile_rosters(), vec!["Anthony Davis".to_string()]);
+        assert_eq!(app.my_players, vec!["LeBron James".to_string(), "Anthony Davis".to_string()]);
+        assert_eq!(app.other_players, vec!["Jamal Murray".to_string()]);
+        assert!(app.reconcile_rosters().is_empty());
+    }
+
+    #[test]
+    fn legacy_draft_files_are_migrated() {
+        let dir = std::env::temp_dir().join(format!("nba_tui_legacy_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let read = |json: &str| {
+            let path = dir.join("my_players.json");
+            std::fs::write(&path, json).unwrap();
+            read_picks(File::open(&path).unwrap())
+                .unwrap()
+                .into_iter()
+                .map(|pick| (pick.name, pick.round))
+                .collect::<Vec<(String, u16)>>()
+        };
+
+        // version 0 held bare names, later bare picks
+        assert_eq!(
+            read(r#"["LeBron James", {"name": "Anthony Davis", "round": 2}]"#),
+            vec![("LeBron James".to_string(), 0), ("Anthony Davis".to_string(), 2)]
+        );
+        assert_eq!(
+            read(r#"{"version": 1, "players": [{"name": "LeBron James", "round": 1}]}"#),
+            vec![("LeBron James".to_string(), 1)]
+        );
+        let path = dir.join("my_players.json");
+        std::fs::write(&path, r#"{"version": 99, "players": []}"#).unwrap();
+        assert!(read_picks(File::open(&path).unwrap()).is_err());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn failed_save_keeps_the_existing_file() {
+        let dir = std::env::temp_dir().join(format!("nba_tui_save_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("my_players.json");
+        save_json_to(&vec!["LeBron James"], &path).unwrap();
+
+        // a directory in the way of the temporary file makes the write fail
+        let tmp_path = dir.join("my_players.json.tmp");
+        std::fs::create_dir(&tmp_path).unwrap();
+        assert!(save_json_to(&vec!["Anthony Davis"], &path).is_err());
+        let saved: Vec<String> = serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(saved, vec!["LeBron James"]);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
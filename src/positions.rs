@@ -1,6 +1,11 @@
 use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+use tui::style::Color;
 
+/// Positions are read with the aliases of `from_str`, so datasets can spell
+/// them out, and written by their variant names.
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(try_from = "String")]
 pub enum Position {
     ANY,
     PG,
@@ -12,20 +17,108 @@ pub enum Position {
     G,
     TALL,
     SHORT,
+    /// Roster slot for any player that counts towards the lineup
+    UTIL,
+    /// Roster slot for any player that doesn't
+    BENCH,
 }
 
 impl Position {
     pub fn does_position_belong(&self, group: &Self) -> bool {
+        if matches!(group, Position::UTIL | Position::BENCH) {
+            return true;
+        }
         match self {
             Position::PG => [Position::PG, Position::G, Position::SHORT, Position::ANY].contains(group),
             Position::SG => [Position::SG, Position::G, Position::SHORT, Position::ANY].contains(group),
             Position::SF => [Position::SF, Position::F, Position::TALL, Position::ANY].contains(group),
             Position::PF => [Position::PF, Position::F, Position::TALL, Position::ANY].contains(group),
             Position::C => [Position::C, Position::TALL, Position::ANY].contains(group),
-            _ => false
+            // players listed with a composite position only belong to that group
+            Position::G => [Position::G, Position::ANY].contains(group),
+            Position::F => [Position::F, Position::ANY].contains(group),
+            Position::TALL => [Position::TALL, Position::ANY].contains(group),
+            Position::SHORT => [Position::SHORT, Position::ANY].contains(group),
+            Position::ANY | Position::UTIL | Position::BENCH => *group == Position::ANY,
+        }
+    }
+
+    /// Whether every player fits a slot of this position.
+    pub fn accepts_anyone(&self) -> bool {
+        matches!(self, Position::ANY | Position::UTIL | Position::BENCH)
+    }
+
+    /// Looks up a position by the keys typed in position jump mode: a single
+    /// letter for positions that are unambiguous, two letters for the rest.
+    pub fn from_shortcut(keys: &str) -> Option<Position> {
+        match keys {
+            "a" => Some(Position::ANY),
+            "pg" => Some(Position::PG),
+            "sg" => Some(Position::SG),
+            "sf" => Some(Position::SF),
+            "pf" => Some(Position::PF),
+            "c" => Some(Position::C),
+            "f" => Some(Position::F),
+            "g" => Some(Position::G),
+            "t" => Some(Position::TALL),
+            "sh" => Some(Position::SHORT),
+            "u" => Some(Position::UTIL),
+            "b" => Some(Position::BENCH),
+            _ => None,
+        }
+    }
+
+    /// Whether the keys typed so far can still be completed to a shortcut.
+    pub fn is_shortcut_prefix(keys: &str) -> bool {
+        matches!(keys, "p" | "s")
+    }
+
+    /// Label for a player's positions with the primary (first listed) one
+    /// first and the others in parentheses, e.g. "SG (PG)".
+    pub fn label(positions: &[Position]) -> String {
+        match positions {
+            [] => String::new(),
+            [primary] => format!("{:?}", primary),
+            [primary, rest @ ..] => format!(
+                "{:?} ({})",
+                primary,
+                rest.iter().map(|p| format!("{:?}", p)).collect::<Vec<String>>().join(", ")
+            ),
         }
     }
 
+    /// Color of the position in lists: guards blue, forwards green, centers magenta.
+    pub fn color(&self) -> Color {
+        match self {
+            Position::PG | Position::SG | Position::G | Position::SHORT => Color::Blue,
+            Position::SF | Position::PF | Position::F => Color::Green,
+            Position::C | Position::TALL => Color::Magenta,
+            Position::ANY | Position::UTIL | Position::BENCH => Color::Reset,
+        }
+    }
+
+    /// Parses a list of positions separated by `|` or `/`, e.g. "PG/SG".
+    pub fn parse_list(s: &str) -> Result<Vec<Position>, String> {
+        s.split(['|', '/']).map(str::parse).collect()
+    }
+
+    /// The position after this one in `get_all_positions`, wrapping around.
+    pub fn next(&self) -> Position {
+        self.offset(1)
+    }
+
+    /// The position before this one in `get_all_positions`, wrapping around.
+    pub fn prev(&self) -> Position {
+        let len = Position::get_all_positions().len();
+        self.offset(len - 1)
+    }
+
+    fn offset(&self, by: usize) -> Position {
+        let positions = Position::get_all_positions();
+        let index = positions.iter().position(|p| p == self).unwrap_or(0);
+        positions[(index + by) % positions.len()].clone()
+    }
+
     pub fn get_all_positions() -> Vec<Position> {
         vec![
             Position::ANY,
@@ -38,7 +131,147 @@ impl Position {
             Position::G,
             Position::TALL,
             Position::SHORT,
+            Position::UTIL,
+            Position::BENCH,
         ]
     }
 }
 
+impl FromStr for Position {
+    type Err = String;
+
+    /// Parses a position by its name or a common alias, ignoring case, e.g.
+    /// "pg", "Point Guard" or "point-guard".
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let words = s
+            .to_lowercase()
+            .split(|c: char| c.is_whitespace() || c == '-' || c == '_')
+            .filter(|word| !word.is_empty())
+            .collect::<Vec<&str>>()
+            .join(" ");
+        match words.as_str() {
+            "any" | "all" => Ok(Position::ANY),
+            "pg" | "point guard" | "point" => Ok(Position::PG),
+            "sg" | "shooting guard" => Ok(Position::SG),
+            "sf" | "small forward" => Ok(Position::SF),
+            "pf" | "power forward" => Ok(Position::PF),
+            "c" | "center" | "centre" => Ok(Position::C),
+            "f" | "forward" => Ok(Position::F),
+            "g" | "guard" => Ok(Position::G),
+            "tall" | "big" => Ok(Position::TALL),
+            "short" | "small" => Ok(Position::SHORT),
+            "util" | "utility" => Ok(Position::UTIL),
+            "bench" | "bn" => Ok(Position::BENCH),
+            _ => Err(format!("unknown position: {:?}", s.trim())),
+        }
+    }
+}
+
+impl TryFrom<String> for Position {
+    type Error = String;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_str_accepts_names_and_aliases() {
+        let aliases = [
+            ("ANY", Position::ANY),
+            ("all", Position::ANY),
+            ("pg", Position::PG),
+            ("Point Guard", Position::PG),
+            ("point-guard", Position::PG),
+            ("point", Position::PG),
+            ("SG", Position::SG),
+            ("shooting_guard", Position::SG),
+            ("sf", Position::SF),
+            ("Small Forward", Position::SF),
+            ("pf", Position::PF),
+            ("power  forward", Position::PF),
+            ("C", Position::C),
+            ("center", Position::C),
+            ("Centre", Position::C),
+            ("f", Position::F),
+            ("forward", Position::F),
+            ("g", Position::G),
+            ("guard", Position::G),
+            ("tall", Position::TALL),
+            ("big", Position::TALL),
+            ("short", Position::SHORT),
+            ("small", Position::SHORT),
+            ("UTIL", Position::UTIL),
+            ("utility", Position::UTIL),
+            ("bench", Position::BENCH),
+            ("BN", Position::BENCH),
+            (" pg ", Position::PG),
+        ];
+        for (s, position) in aliases {
+            assert_eq!(s.parse::<Position>(), Ok(position), "parsing {:?}", s);
+        }
+    }
+
+    #[test]
+    fn from_str_rejects_unknown_positions() {
+        assert_eq!("wing".parse::<Position>(), Err("unknown position: \"wing\"".to_string()));
+        assert_eq!(" point forward ".parse::<Position>(), Err("unknown position: \"point forward\"".to_string()));
+        assert!("".parse::<Position>().is_err());
+        assert_eq!(Position::parse_list("PG/SG"), Ok(vec![Position::PG, Position::SG]));
+        assert!(Position::parse_list("PG|wing").is_err());
+    }
+
+    #[test]
+    fn positions_belong_to_their_groups() {
+        use Position::*;
+        let groups = [
+            (PG, vec![PG, G, SHORT, ANY]),
+            (SG, vec![SG, G, SHORT, ANY]),
+            (SF, vec![SF, F, TALL, ANY]),
+            (PF, vec![PF, F, TALL, ANY]),
+            (C, vec![C, TALL, ANY]),
+            (G, vec![G, ANY]),
+            (F, vec![F, ANY]),
+            (TALL, vec![TALL, ANY]),
+            (SHORT, vec![SHORT, ANY]),
+            (ANY, vec![ANY]),
+            (UTIL, vec![ANY]),
+            (BENCH, vec![ANY]),
+        ];
+        for (position, belongs_to) in groups {
+            for group in Position::get_all_positions() {
+                // every position fits a UTIL or BENCH slot
+                let expected = belongs_to.contains(&group) || matches!(group, UTIL | BENCH);
+                assert_eq!(
+                    position.does_position_belong(&group),
+                    expected,
+                    "{:?} in {:?}",
+                    position,
+                    group
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn next_and_prev_cycle_back_to_the_start() {
+        let positions = Position::get_all_positions();
+        for start in &positions {
+            let mut next = start.clone();
+            let mut prev = start.clone();
+            for _ in 0..positions.len() {
+                next = next.next();
+                prev = prev.prev();
+            }
+            assert_eq!(&next, start);
+            assert_eq!(&prev, start);
+            assert_eq!(&start.next().prev(), start);
+        }
+        assert_eq!(Position::BENCH.next(), Position::ANY);
+        assert_eq!(Position::ANY.prev(), Position::BENCH);
+    }
+}
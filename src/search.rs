@@ -0,0 +1,53 @@
+
+use unicode_normalization::{char::is_combining_mark, UnicodeNormalization};
+
+/// Normalizes a string for matching: accented characters are decomposed and
+/// their accents dropped, and everything is lowercased, so "Dončić" becomes
+/// "doncic".
+pub fn normalize(s: &str) -> String {
+    s.nfd()
+        .filter(|c| !is_combining_mark(*c))
+        .collect::<String>()
+        .to_lowercase()
+}
+
+/// How well the normalized query matches the normalized name: 2 if the name
+/// or one of its words starts with it, 1 if it appears anywhere else, 0 for a
+/// fuzzy-only match.
+pub fn match_rank(query: &str, name: &str) -> u8 {
+    if name.starts_with(query) || name.split(' ').any(|word| word.starts_with(query)) {
+        2
+    } else if name.contains(query) {
+        1
+    } else {
+        0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalize_drops_accents_and_case() {
+        assert_eq!(normalize("Luka Dončić"), "luka doncic");
+        assert_eq!(normalize("Nikola Jokić"), "nikola jokic");
+    }
+
+    #[test]
+    fn match_rank_orders_prefix_substring_fuzzy() {
+        let name = "lebron james";
+        let prefix = match_rank("leb", name);
+        let word_prefix = match_rank("jam", name);
+        let substring = match_rank("bron", name);
+        let fuzzy = match_rank("lbj", name);
+
+        assert_eq!(prefix, 2);
+        assert_eq!(word_prefix, 2);
+        assert_eq!(substring, 1);
+        assert_eq!(fuzzy, 0);
+        assert!(prefix >= word_prefix);
+        assert!(word_prefix > substring);
+        assert!(substring > fuzzy);
+    }
+}